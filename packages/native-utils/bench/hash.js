@@ -28,6 +28,8 @@ const DEFAULT_STATE = {
   appData: '0x0000000000000000000000000000000000000000000000000000000000000000',
 }
 
+const STATES = Array.from({ length: 100 }, (_, i) => ({ ...DEFAULT_STATE, turnNum: i }))
+
 module.exports = () =>
   benny.suite(
     'State hashing',
@@ -44,6 +46,18 @@ module.exports = () =>
       wasm.hashState(DEFAULT_STATE)
     }),
 
+    benny.add('hashState x100 (native)', () => {
+      STATES.forEach(state => native.hashState(state))
+    }),
+
+    benny.add('hashStates x100 (native)', () => {
+      native.hashStates(STATES)
+    }),
+
+    benny.add('hashStates x100 (wasm)', () => {
+      wasm.hashStates(STATES)
+    }),
+
     benny.cycle(),
     benny.complete(),
   )
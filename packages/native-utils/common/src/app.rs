@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use ethereum_types::{Address, U256};
+use lazy_static::lazy_static;
+
+use super::encode::*;
+use super::error::*;
+use super::state::*;
+use super::types::*;
+
+/// The app-specific transition rules of a ForceMove app, i.e. a native
+/// counterpart of an on-chain `ForceMoveApp.validTransition` implementation.
+pub trait ForceMoveApp: Send + Sync {
+    fn valid_transition(&self, from: &State, to: &State, num_participants: usize) -> Result<bool>;
+}
+
+lazy_static! {
+    static ref APPS: RwLock<HashMap<Address, Box<dyn ForceMoveApp>>> = RwLock::new(HashMap::new());
+}
+
+/// Registers a native app implementation for the given app definition, so
+/// that transitions in channels using this app are validated locally rather
+/// than returning `Status::NeedToCheckApp`.
+pub fn register_app(app_definition: Address, app: Box<dyn ForceMoveApp>) {
+    APPS.write().unwrap().insert(app_definition, app);
+}
+
+/// Removes the native app implementation for the given app definition, if
+/// any, returning whether one was registered.
+pub fn unregister_app(app_definition: &Address) -> bool {
+    APPS.write().unwrap().remove(app_definition).is_some()
+}
+
+/// Returns a new instance of the built-in app with the given name, for
+/// registering it under an app definition from JS.
+pub fn builtin_app(name: &str) -> Result<Box<dyn ForceMoveApp>> {
+    match name {
+        "SingleAssetPayments" => Ok(Box::new(SingleAssetPayments)),
+        "CountingApp" => Ok(Box::new(CountingApp)),
+        "TrivialApp" => Ok(Box::new(TrivialApp)),
+        _ => Err(Error::UnknownApp(name.into())),
+    }
+}
+
+/// Validates an app transition with the app registered for the target
+/// state's app definition. Returns `None` if no such app is registered.
+pub(crate) fn validate_app_transition(from: &State, to: &State) -> Option<Result<()>> {
+    let apps = APPS.read().unwrap();
+    let app = apps.get(&to.app_definition)?;
+
+    Some(
+        match app.valid_transition(from, to, to.channel.participants.len()) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(Error::InvalidAppTransition),
+            Err(e) => Err(e),
+        },
+    )
+}
+
+/// A native implementation of nitro-protocol's `SingleAssetPayments` example
+/// app: each participant is entitled to the allocation item at their index,
+/// only the mover may decrease their own balance and the total allocated must
+/// be conserved. Like on chain, participant `turnNum % n` of the target state
+/// is the mover.
+pub struct SingleAssetPayments;
+
+impl SingleAssetPayments {
+    fn allocation(state: &State, num_participants: usize) -> Result<&AllocationAssetOutcome> {
+        let allocation = match state.outcome.0.as_slice() {
+            [AssetOutcome::AllocationAssetOutcome(allocation)] => allocation,
+            [AssetOutcome::GuaranteeAssetOutcome(_)] => return Err(Error::AllocationRequired),
+            assets => return Err(Error::OnlyOneAssetAllowed(assets.len())),
+        };
+
+        if allocation.allocation_items.len() != num_participants {
+            return Err(Error::AllocationLengthMismatch {
+                expected: num_participants,
+                actual: allocation.allocation_items.len(),
+            });
+        }
+
+        Ok(allocation)
+    }
+}
+
+impl ForceMoveApp for SingleAssetPayments {
+    fn valid_transition(&self, from: &State, to: &State, num_participants: usize) -> Result<bool> {
+        let from_items = &Self::allocation(from, num_participants)?.allocation_items;
+        let to_items = &Self::allocation(to, num_participants)?.allocation_items;
+        let mover = to
+            .turn_num
+            .0
+            .checked_rem(num_participants as u64)
+            .ok_or(Error::NoParticipants)? as usize;
+
+        let mut from_total = U256::zero();
+        let mut to_total = U256::zero();
+        for (i, (from_item, to_item)) in from_items.iter().zip(to_items.iter()).enumerate() {
+            if from_item.destination != to_item.destination {
+                return Err(Error::DestinationChanged(i));
+            }
+            if i != mover && to_item.amount.0 < from_item.amount.0 {
+                return Err(Error::NonMoverBalanceDecreased(i));
+            }
+            from_total = from_total
+                .checked_add(from_item.amount.0)
+                .ok_or(Error::TotalAllocatedOverflow)?;
+            to_total = to_total
+                .checked_add(to_item.amount.0)
+                .ok_or(Error::TotalAllocatedOverflow)?;
+        }
+
+        if from_total != to_total {
+            return Err(Error::TotalAllocatedChanged);
+        }
+
+        Ok(true)
+    }
+}
+
+/// A native implementation of nitro-protocol's `CountingApp` test app: the
+/// `appData` is an ABI-encoded `uint256` counter that must be incremented by
+/// one with every turn, while the outcome must not change.
+pub struct CountingApp;
+
+impl ForceMoveApp for CountingApp {
+    fn valid_transition(&self, from: &State, to: &State, _num_participants: usize) -> Result<bool> {
+        let from_counter = Uint256::decode(&from.app_data)?.0;
+        let to_counter = Uint256::decode(&to.app_data)?.0;
+
+        match from_counter.checked_add(U256::one()) {
+            Some(expected) if expected == to_counter => {}
+            expected => {
+                return Err(Error::CounterNotIncremented {
+                    expected: expected.unwrap_or(from_counter),
+                    actual: to_counter,
+                })
+            }
+        }
+
+        if from.outcome != to.outcome {
+            return Err(Error::AppOutcomeChanged);
+        }
+
+        Ok(true)
+    }
+}
+
+/// A native implementation of nitro-protocol's `TrivialApp` test app, which
+/// accepts every transition.
+pub struct TrivialApp;
+
+impl ForceMoveApp for TrivialApp {
+    fn valid_transition(
+        &self,
+        _from: &State,
+        _to: &State,
+        _num_participants: usize,
+    ) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{self, address, private_key, PRIVATE_KEY1};
+    use super::*;
+
+    fn state(app_definition: &str, turn_num: u64, app_data: Vec<u8>, amounts: &[u64]) -> State {
+        State {
+            outcome: fixtures::allocation(amounts),
+            app_definition: address(app_definition),
+            app_data: Bytes(app_data),
+            ..fixtures::state(turn_num)
+        }
+    }
+
+    fn counter(n: u64) -> Vec<u8> {
+        Uint256(U256::from(n)).encode()
+    }
+
+    fn validate_peer_update(from: State, to: State) -> Result<Status> {
+        let signature = to.clone().sign(private_key(PRIVATE_KEY1))?;
+        from.validate_peer_update(to, signature.signature.as_bytes())
+    }
+
+    #[test]
+    fn unregistered_apps_need_checking() {
+        let app = "0x0000000000000000000000000000000000000001";
+
+        assert!(
+            validate_peer_update(state(app, 5, vec![], &[1]), state(app, 6, vec![], &[1]))
+                == Ok(Status::NeedToCheckApp)
+        );
+    }
+
+    #[test]
+    fn trivial_app_accepts_everything() {
+        let app = "0x0000000000000000000000000000000000000002";
+        register_app(address(app), builtin_app("TrivialApp").unwrap());
+
+        assert!(
+            validate_peer_update(state(app, 5, vec![1], &[1]), state(app, 6, vec![2], &[3]))
+                == Ok(Status::True)
+        );
+    }
+
+    #[test]
+    fn counting_app_requires_increment() {
+        let app = "0x0000000000000000000000000000000000000003";
+        register_app(address(app), builtin_app("CountingApp").unwrap());
+
+        assert!(
+            validate_peer_update(
+                state(app, 5, counter(1), &[1]),
+                state(app, 6, counter(2), &[1])
+            ) == Ok(Status::True)
+        );
+        assert_eq!(
+            validate_peer_update(
+                state(app, 5, counter(1), &[1]),
+                state(app, 6, counter(3), &[1])
+            )
+            .err()
+            .map(|e| e.code()),
+            Some("COUNTER_NOT_INCREMENTED")
+        );
+        assert_eq!(
+            validate_peer_update(
+                state(app, 5, counter(1), &[1]),
+                state(app, 6, counter(2), &[2])
+            )
+            .err()
+            .map(|e| e.code()),
+            Some("APP_OUTCOME_CHANGED")
+        );
+        assert_eq!(
+            validate_peer_update(
+                state(app, 5, vec![1], &[1]),
+                state(app, 6, counter(2), &[1])
+            )
+            .err()
+            .map(|e| e.code()),
+            Some("INVALID_ENCODING")
+        );
+    }
+
+    #[test]
+    fn single_asset_payments_only_lets_the_mover_pay() {
+        let app = "0x0000000000000000000000000000000000000005";
+        register_app(address(app), builtin_app("SingleAssetPayments").unwrap());
+
+        // Participant 0 moves in turn 6, so may pay participant 1 but not
+        // take from them.
+        assert!(
+            validate_peer_update(
+                state(app, 5, vec![], &[5, 5]),
+                state(app, 6, vec![], &[3, 7])
+            ) == Ok(Status::True)
+        );
+        assert_eq!(
+            validate_peer_update(
+                state(app, 5, vec![], &[5, 5]),
+                state(app, 6, vec![], &[7, 3])
+            )
+            .err(),
+            Some(Error::NonMoverBalanceDecreased(1))
+        );
+    }
+
+    #[test]
+    fn single_asset_payments_follows_the_contract() {
+        let app = "0x0000000000000000000000000000000000000006";
+        let valid_transition = |from: &[u64], to: &[u64], turn_num: u64| {
+            SingleAssetPayments.valid_transition(
+                &state(app, turn_num - 1, vec![], from),
+                &state(app, turn_num, vec![], to),
+                from.len(),
+            )
+        };
+
+        // nitro-protocol's SingleAssetPayments takes `turnNumB % nParticipants`
+        // to be the mover.
+        assert_eq!(valid_transition(&[5, 5], &[3, 7], 6), Ok(true));
+        assert_eq!(valid_transition(&[5, 5], &[7, 3], 7), Ok(true));
+        assert_eq!(
+            valid_transition(&[5, 5], &[7, 3], 6),
+            Err(Error::NonMoverBalanceDecreased(1))
+        );
+        assert_eq!(valid_transition(&[], &[], 6), Err(Error::NoParticipants));
+    }
+
+    #[test]
+    fn unregistered_apps_are_removed() {
+        let app = "0x0000000000000000000000000000000000000004";
+        register_app(address(app), builtin_app("CountingApp").unwrap());
+
+        assert!(unregister_app(&address(app)));
+        assert!(!unregister_app(&address(app)));
+        assert!(
+            validate_peer_update(
+                state(app, 5, counter(1), &[1]),
+                state(app, 6, counter(5), &[1])
+            ) == Ok(Status::NeedToCheckApp)
+        );
+    }
+}
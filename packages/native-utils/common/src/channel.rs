@@ -2,27 +2,39 @@ use ethabi::{encode, Token};
 use ethereum_types::{Address};
 use serde_derive::*;
 
+use super::error::*;
+use super::serde::*;
 use super::tokenize::*;
 use super::types::*;
 use super::utils::*;
 use super::state::*;
 
-//#[derive(Deserialize)]
-//#[serde(rename_all = "camelCase")]
-pub struct SignedStateVarsWithHash {
+/// The parts of a state, besides the channel itself, that must not change
+/// over the lifetime of a channel.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixedPart {
+    #[serde(deserialize_with = "deserialize_address")]
+    pub app_definition: Address,
+    pub challenge_duration: Uint48,
+}
+
+/// The parts of a state that may change from one turn to the next.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateVariables {
     pub turn_num: Uint48,
     pub is_final: bool,
     pub outcome: Outcome,
     pub app_data: Bytes,
-    pub hash: Bytes32,
-    pub signature: RecoverableSignature
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Channel {
     pub chain_id: Uint256,
     pub channel_nonce: Uint256,
+    #[serde(serialize_with = "serialize_addresses", deserialize_with = "deserialize_addresses")]
     pub participants: Vec<Address>,
 }
 
@@ -44,4 +56,177 @@ impl Channel {
         )
         .into()
     }
-}
\ No newline at end of file
+    /// Checks that a sequence of states is supported by the signatures of all
+    /// participants, mirroring ForceMove's `_requireStateSupportedBy`.
+    ///
+    /// `signatures[i]` is participant `i`'s signature on the state at index
+    /// `who_signed_what[i]` of `variable_parts`.
+    pub fn validate_support_proof(
+        &self,
+        fixed_part: FixedPart,
+        variable_parts: Vec<StateVariables>,
+        signatures: Vec<Bytes>,
+        who_signed_what: Vec<u8>,
+    ) -> Result<Status> {
+        let n_participants = self.participants.len();
+        let n_states = variable_parts.len();
+
+        if n_participants == 0 {
+            return Err(Error::NoParticipants);
+        }
+        if n_states == 0 {
+            return Err(Error::EmptySupportProof);
+        }
+        if signatures.len() != n_participants {
+            return Err(Error::SignatureCountMismatch {
+                expected: n_participants,
+                actual: signatures.len(),
+            });
+        }
+        if who_signed_what.len() != n_participants {
+            return Err(Error::WhoSignedWhatLengthMismatch {
+                expected: n_participants,
+                actual: who_signed_what.len(),
+            });
+        }
+
+        let states: Vec<State> = variable_parts
+            .into_iter()
+            .map(|variables| State {
+                turn_num: variables.turn_num,
+                is_final: variables.is_final,
+                channel: self.clone(),
+                challenge_duration: fixed_part.challenge_duration.clone(),
+                outcome: variables.outcome,
+                app_definition: fixed_part.app_definition,
+                app_data: variables.app_data,
+            })
+            .collect();
+
+        let mut status = Status::True;
+        for pair in states.windows(2) {
+            if pair[0].require_valid_protocol_transition(&pair[1])? == Status::NeedToCheckApp {
+                status = Status::NeedToCheckApp;
+            }
+        }
+
+        // Every participant must have signed the state they are responsible
+        // for, or a later one.
+        let largest_turn_num = states[n_states - 1].turn_num.0;
+        let mover = (largest_turn_num % n_participants as u64) as usize;
+        for (i, who) in who_signed_what.iter().enumerate() {
+            let offset = (n_participants + mover - i) % n_participants;
+            if *who as usize + offset + 1 < n_states || *who as usize >= n_states {
+                return Err(Error::UnacceptableWhoSignedWhat);
+            }
+        }
+
+        for (i, signature) in signatures.into_iter().enumerate() {
+            let expected = checksum_address(self.participants[i].0.to_vec());
+            let recovered = states[who_signed_what[i] as usize].recover_address(signature)?;
+            if recovered != expected {
+                return Err(Error::SignerMismatch { expected, recovered });
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{self, private_key, PRIVATE_KEY1, PRIVATE_KEY2};
+    use super::*;
+
+    fn fixed_part() -> FixedPart {
+        let state = fixtures::state(0);
+        FixedPart {
+            app_definition: state.app_definition,
+            challenge_duration: state.challenge_duration,
+        }
+    }
+
+    fn variable_part(turn_num: u64) -> StateVariables {
+        let state = fixtures::state(turn_num);
+        StateVariables {
+            turn_num: state.turn_num,
+            is_final: state.is_final,
+            outcome: state.outcome,
+            app_data: state.app_data,
+        }
+    }
+
+    fn sign(turn_num: u64, key: &str) -> Bytes {
+        let state_signature = fixtures::state(turn_num).sign(private_key(key)).unwrap();
+        state_signature.signature.as_bytes()
+    }
+
+    fn validate_support_proof(
+        turn_nums: &[u64],
+        signatures: Vec<Bytes>,
+        who_signed_what: Vec<u8>,
+    ) -> Result<Status> {
+        fixtures::channel().validate_support_proof(
+            fixed_part(),
+            turn_nums.iter().cloned().map(variable_part).collect(),
+            signatures,
+            who_signed_what,
+        )
+    }
+
+    #[test]
+    fn supports_a_state_signed_by_everyone() {
+        let signatures = vec![sign(5, PRIVATE_KEY1), sign(5, PRIVATE_KEY2)];
+
+        assert!(validate_support_proof(&[5], signatures, vec![0, 0]) == Ok(Status::True));
+    }
+
+    #[test]
+    fn supports_each_participant_signing_their_own_turn() {
+        let signatures = vec![sign(4, PRIVATE_KEY1), sign(5, PRIVATE_KEY2)];
+
+        assert!(
+            validate_support_proof(&[4, 5], signatures, vec![0, 1]) == Ok(Status::NeedToCheckApp)
+        );
+    }
+
+    #[test]
+    fn rejects_a_participant_signing_before_their_turn() {
+        let signatures = vec![sign(4, PRIVATE_KEY1), sign(4, PRIVATE_KEY2)];
+
+        assert!(
+            validate_support_proof(&[4, 5], signatures, vec![0, 0])
+                == Err(Error::UnacceptableWhoSignedWhat)
+        );
+    }
+
+    #[test]
+    fn rejects_signatures_by_the_wrong_participant() {
+        let signatures = vec![sign(5, PRIVATE_KEY2), sign(5, PRIVATE_KEY2)];
+
+        assert!(matches!(
+            validate_support_proof(&[5], signatures, vec![0, 0]),
+            Err(Error::SignerMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_channels_without_participants() {
+        let channel = Channel {
+            participants: vec![],
+            ..fixtures::channel()
+        };
+
+        assert!(
+            channel.validate_support_proof(fixed_part(), vec![variable_part(5)], vec![], vec![])
+                == Err(Error::NoParticipants)
+        );
+    }
+
+    #[test]
+    fn supports_the_largest_turn_number_without_overflowing() {
+        let signatures = vec![sign(u64::MAX, PRIVATE_KEY1), sign(u64::MAX, PRIVATE_KEY2)];
+
+        assert!(validate_support_proof(&[u64::MAX], signatures, vec![0, 0]) == Ok(Status::True));
+    }
+}
@@ -1,5 +1,6 @@
-use ethabi::encode;
+use ethabi::{decode, encode};
 
+use super::error::*;
 use super::tokenize::*;
 
 pub trait Encode {
@@ -11,3 +12,17 @@ impl<T: Tokenize> Encode for T {
         encode(&[self.tokenize()])
     }
 }
+
+pub trait Decode: Sized {
+    fn decode(bytes: &[u8]) -> Result<Self>;
+}
+
+impl<T: Detokenize> Decode for T {
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let token = decode(&[T::param_type()], bytes)
+            .map_err(|e| Error::InvalidEncoding(e.to_string()))?
+            .pop()
+            .ok_or_else(|| Error::InvalidEncoding("no value decoded".into()))?;
+        T::detokenize(token)
+    }
+}
@@ -0,0 +1,206 @@
+use ethereum_types::U256;
+use thiserror::Error;
+
+use super::outcome::OutcomeIssue;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("invalid private key")]
+    InvalidPrivateKey,
+
+    #[error("invalid public key")]
+    InvalidPublicKey,
+
+    #[error("invalid seed length: expected 16 to 64 bytes, got {0}")]
+    InvalidSeedLength(usize),
+
+    #[error("invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+
+    #[error("invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+
+    #[error("derived key is invalid")]
+    InvalidDerivedKey,
+
+    #[error("invalid signature length: expected 64 or 65 bytes, got {0}")]
+    InvalidSignatureLength(usize),
+
+    #[error("invalid recovery ID: {0}")]
+    InvalidRecoveryId(u8),
+
+    #[error("invalid signature")]
+    InvalidSignature,
+
+    #[error("Signature verification failed: expected signer {expected}, recovered {recovered}")]
+    SignerMismatch { expected: String, recovered: String },
+
+    #[error("channel has no participants")]
+    NoParticipants,
+
+    #[error("turnNum must increment by one: expected {expected}, got {actual}")]
+    TurnNumNotIncremented { expected: u64, actual: u64 },
+
+    #[error("chainId must not change")]
+    ChainIdChanged,
+
+    #[error("channelNonce must not change")]
+    ChannelNonceChanged,
+
+    #[error("appDefinition must not change: {from} -> {to}")]
+    AppDefinitionChanged { from: String, to: String },
+
+    #[error("challengeDuration must not change: {from} -> {to}")]
+    ChallengeDurationChanged { from: u64, to: u64 },
+
+    #[error("transition from a final state to a non-final state")]
+    FinalToNonFinal,
+
+    #[error("Outcome change forbidden")]
+    OutcomeChanged,
+
+    #[error("appData change forbidden")]
+    AppDataChanged,
+
+    #[error("invalid app transition")]
+    InvalidAppTransition,
+
+    #[error("unknown app: {0}")]
+    UnknownApp(String),
+
+    #[error("Only one asset allowed: got {0}")]
+    OnlyOneAssetAllowed(usize),
+
+    #[error("AssetOutcomeType must be Allocation")]
+    AllocationRequired,
+
+    #[error("|Allocation|!=|participants|: expected {expected}, got {actual}")]
+    AllocationLengthMismatch { expected: usize, actual: usize },
+
+    #[error("Destinations may not change: allocation item {0}")]
+    DestinationChanged(usize),
+
+    #[error("Nonmover balance decreased: allocation item {0}")]
+    NonMoverBalanceDecreased(usize),
+
+    #[error("Total allocated cannot change")]
+    TotalAllocatedChanged,
+
+    #[error("Total allocated overflows uint256")]
+    TotalAllocatedOverflow,
+
+    #[error("Counter must be incremented: expected {expected}, got {actual}")]
+    CounterNotIncremented { expected: U256, actual: U256 },
+
+    #[error("Outcome must not change")]
+    AppOutcomeChanged,
+
+    #[error("expected one signature per state: expected {expected}, got {actual}")]
+    BatchLengthMismatch { expected: usize, actual: usize },
+
+    #[error("support proof must contain at least one state")]
+    EmptySupportProof,
+
+    #[error("expected one signature per participant: expected {expected}, got {actual}")]
+    SignatureCountMismatch { expected: usize, actual: usize },
+
+    #[error("whoSignedWhat must be the same length as participants: expected {expected}, got {actual}")]
+    WhoSignedWhatLengthMismatch { expected: usize, actual: usize },
+
+    #[error("Unacceptable whoSignedWhat array")]
+    UnacceptableWhoSignedWhat,
+
+    #[error("{path}: {message}")]
+    InvalidInput { path: String, message: String },
+
+    #[error("invalid address checksum at {path}: got {address}, expected {expected}")]
+    InvalidAddressChecksum {
+        path: String,
+        address: String,
+        expected: String,
+    },
+
+    #[error("invalid typed data: {0}")]
+    InvalidTypedData(String),
+
+    #[error("invalid ABI encoding: {0}")]
+    InvalidEncoding(String),
+
+    #[error("unknown asset outcome type: {0}")]
+    UnknownAssetOutcomeType(U256),
+
+    #[error("indices must be strictly increasing")]
+    IndicesNotIncreasing,
+
+    #[error("index out of range: {index} >= {length}")]
+    IndexOutOfRange { index: usize, length: usize },
+
+    #[error("destination not found in allocation: {0}")]
+    DestinationNotFound(String),
+
+    #[error("invalid outcome: {}", format_issues(.0))]
+    InvalidOutcome(Vec<OutcomeIssue>),
+}
+
+impl Error {
+    /// A stable, machine-readable identifier for the error, surfaced to JS
+    /// as the `code` property of thrown errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidPrivateKey => "INVALID_PRIVATE_KEY",
+            Self::InvalidPublicKey => "INVALID_PUBLIC_KEY",
+            Self::InvalidSeedLength(_) => "INVALID_SEED_LENGTH",
+            Self::InvalidMnemonic(_) => "INVALID_MNEMONIC",
+            Self::InvalidDerivationPath(_) => "INVALID_DERIVATION_PATH",
+            Self::InvalidDerivedKey => "INVALID_DERIVED_KEY",
+            Self::InvalidSignatureLength(_) => "INVALID_SIGNATURE_LENGTH",
+            Self::InvalidRecoveryId(_) => "INVALID_RECOVERY_ID",
+            Self::InvalidSignature => "INVALID_SIGNATURE",
+            Self::SignerMismatch { .. } => "SIGNER_MISMATCH",
+            Self::NoParticipants => "NO_PARTICIPANTS",
+            Self::TurnNumNotIncremented { .. } => "TURN_NUM_NOT_INCREMENTED",
+            Self::ChainIdChanged => "CHAIN_ID_CHANGED",
+            Self::ChannelNonceChanged => "CHANNEL_NONCE_CHANGED",
+            Self::AppDefinitionChanged { .. } => "APP_DEFINITION_CHANGED",
+            Self::ChallengeDurationChanged { .. } => "CHALLENGE_DURATION_CHANGED",
+            Self::FinalToNonFinal => "FINAL_TO_NON_FINAL",
+            Self::OutcomeChanged => "OUTCOME_CHANGED",
+            Self::AppDataChanged => "APP_DATA_CHANGED",
+            Self::InvalidAppTransition => "INVALID_APP_TRANSITION",
+            Self::UnknownApp(_) => "UNKNOWN_APP",
+            Self::OnlyOneAssetAllowed(_) => "ONLY_ONE_ASSET_ALLOWED",
+            Self::AllocationRequired => "ALLOCATION_REQUIRED",
+            Self::AllocationLengthMismatch { .. } => "ALLOCATION_LENGTH_MISMATCH",
+            Self::DestinationChanged(_) => "DESTINATION_CHANGED",
+            Self::NonMoverBalanceDecreased(_) => "NON_MOVER_BALANCE_DECREASED",
+            Self::TotalAllocatedChanged => "TOTAL_ALLOCATED_CHANGED",
+            Self::TotalAllocatedOverflow => "TOTAL_ALLOCATED_OVERFLOW",
+            Self::CounterNotIncremented { .. } => "COUNTER_NOT_INCREMENTED",
+            Self::AppOutcomeChanged => "APP_OUTCOME_CHANGED",
+            Self::BatchLengthMismatch { .. } => "BATCH_LENGTH_MISMATCH",
+            Self::EmptySupportProof => "EMPTY_SUPPORT_PROOF",
+            Self::SignatureCountMismatch { .. } => "SIGNATURE_COUNT_MISMATCH",
+            Self::WhoSignedWhatLengthMismatch { .. } => "WHO_SIGNED_WHAT_LENGTH_MISMATCH",
+            Self::UnacceptableWhoSignedWhat => "UNACCEPTABLE_WHO_SIGNED_WHAT",
+            Self::InvalidInput { .. } => "INVALID_INPUT",
+            Self::InvalidAddressChecksum { .. } => "INVALID_ADDRESS_CHECKSUM",
+            Self::InvalidTypedData(_) => "INVALID_TYPED_DATA",
+            Self::InvalidEncoding(_) => "INVALID_ENCODING",
+            Self::UnknownAssetOutcomeType(_) => "UNKNOWN_ASSET_OUTCOME_TYPE",
+            Self::IndicesNotIncreasing => "INDICES_NOT_INCREASING",
+            Self::IndexOutOfRange { .. } => "INDEX_OUT_OF_RANGE",
+            Self::DestinationNotFound(_) => "DESTINATION_NOT_FOUND",
+            Self::InvalidOutcome(_) => "INVALID_OUTCOME",
+        }
+    }
+}
+
+fn format_issues(issues: &[OutcomeIssue]) -> String {
+    issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -0,0 +1,61 @@
+//! Fixtures shared by the unit tests of the other modules.
+
+use ethereum_types::{Address, U256};
+
+use super::channel::*;
+use super::state::*;
+use super::types::*;
+
+/// The private key of participant 0 of `channel()`.
+pub const PRIVATE_KEY1: &str = "0x8da4ef21b864d2cc526dbdb2a120bd2874c36c9d0a1fb7f8c63d7f7a8b41de8f";
+/// The private key of participant 1 of `channel()`.
+pub const PRIVATE_KEY2: &str = "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d";
+
+pub const ADDRESS1: &str = "0x63FaC9201494f0bd17B9892B9fae4d52fe3BD377";
+pub const ADDRESS2: &str = "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1";
+
+pub fn private_key(private_key: &str) -> Bytes {
+    Bytes(hex::decode(private_key.trim_start_matches("0x")).unwrap())
+}
+
+pub fn address(address: &str) -> Address {
+    address.trim_start_matches("0x").parse().unwrap()
+}
+
+/// A two-party channel between `ADDRESS1` and `ADDRESS2`.
+pub fn channel() -> Channel {
+    Channel {
+        chain_id: Uint256(U256::one()),
+        channel_nonce: Uint256(U256::one()),
+        participants: vec![address(ADDRESS1), address(ADDRESS2)],
+    }
+}
+
+/// A state of `channel()` with an empty outcome and no app; override fields
+/// with struct update syntax, e.g. `State { is_final: true, ..state(5) }`.
+pub fn state(turn_num: u64) -> State {
+    State {
+        turn_num: Uint48(turn_num),
+        is_final: false,
+        channel: channel(),
+        challenge_duration: Uint48(1),
+        outcome: Outcome::new(vec![]),
+        app_definition: Address::zero(),
+        app_data: Bytes(vec![]),
+    }
+}
+
+/// An outcome allocating `amounts[i]` to participant `i` of `channel()`.
+pub fn allocation(amounts: &[u64]) -> Outcome {
+    let participants = channel().participants;
+    amounts
+        .iter()
+        .zip(&participants)
+        .fold(
+            Outcome::builder().allocation(Address::zero()),
+            |allocation, (amount, participant)| {
+                allocation.item(Bytes32::from_address(participant), *amount)
+            },
+        )
+        .build()
+}
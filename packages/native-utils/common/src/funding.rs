@@ -0,0 +1,123 @@
+use ethereum_types::U256;
+use serde_derive::*;
+
+use super::error::*;
+use super::state::*;
+use super::types::*;
+
+/// Whether, and how much, a participant may deposit for a destination, as
+/// computed by `AllocationAssetOutcome::deposit_safety`.
+#[derive(Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositSafety {
+    /// The holdings at which it is safe to deposit, i.e. the total allocated
+    /// to the items ahead of the destination. Pass this as `expectedHeld` to
+    /// the AssetHolder's `deposit`.
+    pub expected_held: Uint256,
+    /// The amount left to deposit for the destination once it is safe to do
+    /// so, taking into account what others have already deposited.
+    pub amount_required: Uint256,
+    /// Whether everyone ahead of the destination has deposited.
+    pub safe_to_deposit: bool,
+}
+
+impl AllocationAssetOutcome {
+    /// Computes the deposit threshold for the first item allocated to
+    /// `destination`, following the direct-funding protocol: a participant
+    /// deposits only once the `holdings` cover everything allocated ahead of
+    /// them.
+    pub fn deposit_safety(
+        &self,
+        destination: &Bytes32,
+        holdings: &Uint256,
+    ) -> Result<DepositSafety> {
+        let mut expected_held = U256::zero();
+
+        for item in &self.allocation_items {
+            if &item.destination == destination {
+                let target = expected_held
+                    .checked_add(item.amount.0)
+                    .ok_or(Error::TotalAllocatedOverflow)?;
+                let safe_to_deposit = holdings.0 >= expected_held;
+                let amount_required = target.saturating_sub(holdings.0.max(expected_held));
+
+                return Ok(DepositSafety {
+                    expected_held: Uint256(expected_held),
+                    amount_required: Uint256(amount_required),
+                    safe_to_deposit,
+                });
+            }
+
+            expected_held = expected_held
+                .checked_add(item.amount.0)
+                .ok_or(Error::TotalAllocatedOverflow)?;
+        }
+
+        Err(Error::DestinationNotFound(
+            destination.to_vec().to_hex_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn destination(i: u64) -> Bytes32 {
+        serde_json::from_value(json!(format!("0x{:064x}", i))).unwrap()
+    }
+
+    fn outcome() -> AllocationAssetOutcome {
+        serde_json::from_value(json!({
+            "assetHolderAddress": "0x0000000000000000000000000000000000000001",
+            "allocationItems": [
+                { "destination": format!("0x{:064x}", 1), "amount": 5 },
+                { "destination": format!("0x{:064x}", 2), "amount": 3 },
+                { "destination": format!("0x{:064x}", 3), "amount": 2 },
+            ],
+        }))
+        .unwrap()
+    }
+
+    fn check(index: u64, holdings: u64, expected: (u64, u64, bool)) {
+        let safety = outcome()
+            .deposit_safety(&destination(index), &Uint256(holdings.into()))
+            .unwrap();
+        assert_eq!(
+            (
+                safety.expected_held.0,
+                safety.amount_required.0,
+                safety.safe_to_deposit
+            ),
+            (expected.0.into(), expected.1.into(), expected.2)
+        );
+    }
+
+    #[test]
+    fn first_participant_deposits_immediately() {
+        check(1, 0, (0, 5, true));
+        check(1, 2, (0, 3, true));
+        check(1, 5, (0, 0, true));
+    }
+
+    #[test]
+    fn later_participants_wait_for_those_ahead() {
+        check(2, 0, (5, 3, false));
+        check(2, 4, (5, 3, false));
+        check(2, 5, (5, 3, true));
+        check(3, 9, (8, 1, true));
+        check(3, 12, (8, 0, true));
+    }
+
+    #[test]
+    fn rejects_unknown_destinations() {
+        assert!(
+            outcome()
+                .deposit_safety(&destination(4), &Uint256(0.into()))
+                .err()
+                == Some(Error::DestinationNotFound(format!("0x{:064x}", 4)))
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use bip39::{Language, Mnemonic, Seed};
+use hmac::{Hmac, Mac, NewMac};
+use secp256k1::{PublicKey, SecretKey};
+use serde_derive::*;
+use sha2::Sha512;
+
+use super::error::*;
+use super::types::*;
+use super::utils::*;
+
+/// The BIP-44 path of Ethereum accounts, minus the address index.
+const ETHEREUM_PATH: &str = "m/44'/60'/0'/0";
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// A private key derived from a seed or mnemonic, along with its address.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivedKey {
+    pub private_key: Bytes,
+    pub address: String,
+}
+
+impl DerivedKey {
+    fn new(secret_key: SecretKey) -> Self {
+        let public_key = PublicKey::from_secret_key(&secret_key);
+        Self {
+            private_key: Bytes(secret_key.serialize().to_vec()),
+            address: checksum_address(public_key_to_address(public_key)),
+        }
+    }
+}
+
+struct ExtendedKey {
+    secret_key: SecretKey,
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKey {
+    fn from_hmac(key: &[u8], data: &[u8]) -> Result<Self> {
+        let mut mac = Hmac::<Sha512>::new_varkey(key).expect("HMAC accepts keys of any length");
+        mac.update(data);
+        let output = mac.finalize().into_bytes();
+
+        let secret_key = SecretKey::parse_slice(&output[..32]).or(Err(Error::InvalidDerivedKey))?;
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&output[32..]);
+
+        Ok(Self {
+            secret_key,
+            chain_code,
+        })
+    }
+
+    /// BIP-32 master key generation.
+    fn master(seed: &[u8]) -> Result<Self> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err(Error::InvalidSeedLength(seed.len()));
+        }
+        Self::from_hmac(b"Bitcoin seed", seed)
+    }
+
+    /// BIP-32 private parent key to private child key derivation.
+    fn child(&self, index: u32) -> Result<Self> {
+        let mut data = Vec::with_capacity(37);
+        if index >= HARDENED {
+            data.push(0);
+            data.extend_from_slice(&self.secret_key.serialize());
+        } else {
+            data.extend_from_slice(
+                &PublicKey::from_secret_key(&self.secret_key).serialize_compressed(),
+            );
+        }
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let mut child = Self::from_hmac(&self.chain_code, &data)?;
+        child
+            .secret_key
+            .tweak_add_assign(&self.secret_key)
+            .or(Err(Error::InvalidDerivedKey))?;
+
+        Ok(child)
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<u32>> {
+    let invalid = || Error::InvalidDerivationPath(path.into());
+
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(invalid());
+    }
+
+    segments
+        .map(|segment| {
+            let (index, offset) = match segment.strip_suffix('\'') {
+                Some(index) => (index, HARDENED),
+                None => (segment, 0),
+            };
+            match index.parse::<u32>() {
+                Ok(index) if index < HARDENED => Ok(index + offset),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+/// Derives the private key at the given BIP-32 path (e.g. `m/44'/60'/0'/0/0`)
+/// from a seed of 16 to 64 bytes.
+pub fn derive_key(seed: &[u8], path: &str) -> Result<DerivedKey> {
+    let mut key = ExtendedKey::master(seed)?;
+    for index in parse_path(path)? {
+        key = key.child(index)?;
+    }
+    Ok(DerivedKey::new(key.secret_key))
+}
+
+/// Deterministically generates the `index`-th Ethereum key from a seed, i.e.
+/// the key at `m/44'/60'/0'/0/index`.
+pub fn derive_key_from_seed(seed: &[u8], index: u32) -> Result<DerivedKey> {
+    derive_key(seed, &format!("{}/{}", ETHEREUM_PATH, index))
+}
+
+/// Converts an English BIP-39 mnemonic into its seed, validating its words
+/// and checksum.
+pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> Result<Vec<u8>> {
+    let mnemonic = Mnemonic::from_phrase(mnemonic, Language::English)
+        .map_err(|error| Error::InvalidMnemonic(error.to_string()))?;
+    Ok(Seed::new(&mnemonic, passphrase).as_bytes().to_vec())
+}
+
+/// Derives the `index`-th Ethereum key from a BIP-39 mnemonic, i.e. the key at
+/// `m/44'/60'/0'/0/index`, as wallets like MetaMask do.
+pub fn derive_key_from_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+    index: u32,
+) -> Result<DerivedKey> {
+    derive_key_from_seed(&mnemonic_to_seed(mnemonic, passphrase)?, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_bip32_test_vector() {
+        // Test vector 1 from BIP-32.
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+
+        let key = derive_key(&seed, "m").unwrap();
+        assert_eq!(
+            hex::encode(key.private_key.0),
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+        );
+
+        let key = derive_key(&seed, "m/0'/1/2'/2/1000000000").unwrap();
+        assert_eq!(
+            hex::encode(key.private_key.0),
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8"
+        );
+    }
+
+    #[test]
+    fn derives_ethereum_keys_from_mnemonic() {
+        let mnemonic = "test test test test test test test test test test test junk";
+
+        let key = derive_key_from_mnemonic(mnemonic, "", 0).unwrap();
+        assert_eq!(
+            hex::encode(key.private_key.0),
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        );
+        assert_eq!(key.address, "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+        let key = derive_key_from_mnemonic(mnemonic, "", 1).unwrap();
+        assert_eq!(key.address, "0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(
+            derive_key(&[0; 8], "m").err(),
+            Some(Error::InvalidSeedLength(8))
+        );
+        assert_eq!(
+            derive_key(&[0; 16], "m/44'/x").err(),
+            Some(Error::InvalidDerivationPath("m/44'/x".into()))
+        );
+        assert_eq!(
+            derive_key(&[0; 16], "44'/60'").err(),
+            Some(Error::InvalidDerivationPath("44'/60'".into()))
+        );
+        assert!(matches!(
+            derive_key_from_mnemonic(
+                "test test test test test test test test test test test test",
+                "",
+                0
+            ),
+            Err(Error::InvalidMnemonic(_))
+        ));
+    }
+}
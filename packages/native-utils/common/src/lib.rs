@@ -1,15 +1,44 @@
+mod app;
 mod encode;
+mod error;
+#[cfg(test)]
+mod fixtures;
+mod funding;
+mod keys;
+mod outcome;
+mod payouts;
 mod serde;
+mod signer;
 mod state;
 mod channel;
 mod tokenize;
+mod typed_data;
 mod types;
 mod utils;
 
 pub mod prelude {
-    pub use super::encode::Encode;
+    pub use ethereum_types::Address;
+    pub use secp256k1::SecretKey;
+
+    pub use super::app::{
+        builtin_app, register_app, unregister_app, CountingApp, ForceMoveApp, SingleAssetPayments,
+        TrivialApp,
+    };
+    pub use super::encode::{Decode, Encode};
+    pub use super::error::Error;
+    pub use super::funding::DepositSafety;
+    pub use super::outcome::{AllocationBuilder, OutcomeBuilder, OutcomeIssue};
+    pub use super::payouts::{ClaimPayouts, TransferPayouts};
+    pub use super::serde::{parse_value, ParseOptions};
+    pub use super::keys::{
+        derive_key, derive_key_from_mnemonic, derive_key_from_seed, mnemonic_to_seed, DerivedKey,
+    };
+    pub use super::signer::Signer;
     pub use super::state::*;
-    pub use super::tokenize::Tokenize;
+    pub use super::tokenize::{Detokenize, Tokenize};
+    pub use super::typed_data::{
+        encode_type, hash_struct, type_hash, TypedData, TypedDataField, TypedDataTypes,
+    };
     pub use super::types::*;
     pub use super::utils::*;
     pub use super::channel::*;
@@ -0,0 +1,408 @@
+use std::slice;
+
+use ethereum_types::{Address, U256};
+use thiserror::Error;
+
+use super::state::*;
+use super::types::*;
+use super::utils::checksum_address;
+
+/// A problem with an outcome that the contracts would not reject, but that
+/// likely indicates a bug or a malicious peer.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum OutcomeIssue {
+    #[error("duplicate asset holder {0}")]
+    DuplicateAssetHolder(String),
+
+    #[error("duplicate destination {destination} for asset holder {asset_holder}")]
+    DuplicateDestination {
+        asset_holder: String,
+        destination: String,
+    },
+
+    #[error("total allocated for asset holder {0} overflows uint256")]
+    TotalOverflow(String),
+
+    #[error("guarantee for asset holder {0} targets its own channel")]
+    SelfGuarantee(String),
+}
+
+impl Outcome {
+    pub fn new(assets: Vec<AssetOutcome>) -> Self {
+        Self(assets)
+    }
+
+    /// Starts building an outcome, e.g.
+    ///
+    /// ```
+    /// # use statechannels_native_utils_common::prelude::*;
+    /// # let (eth_asset_holder, alice, bob) = (Address::zero(), Address::zero(), Address::zero());
+    /// let outcome = Outcome::builder()
+    ///     .allocation(eth_asset_holder)
+    ///     .item(Bytes32::from_address(&alice), 5)
+    ///     .item(Bytes32::from_address(&bob), 3)
+    ///     .build();
+    /// ```
+    pub fn builder() -> OutcomeBuilder {
+        OutcomeBuilder::default()
+    }
+
+    pub fn assets(&self) -> slice::Iter<'_, AssetOutcome> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The asset outcome for the given asset holder, if any.
+    pub fn asset(&self, asset_holder_address: &Address) -> Option<&AssetOutcome> {
+        self.assets()
+            .find(|asset| asset.asset_holder_address() == asset_holder_address)
+    }
+
+    pub fn allocations(&self) -> impl Iterator<Item = &AllocationAssetOutcome> {
+        self.assets().filter_map(AssetOutcome::as_allocation)
+    }
+
+    pub fn guarantees(&self) -> impl Iterator<Item = &GuaranteeAssetOutcome> {
+        self.assets().filter_map(AssetOutcome::as_guarantee)
+    }
+
+    pub fn into_assets(self) -> Vec<AssetOutcome> {
+        self.0
+    }
+
+    /// Checks the outcome of the channel with the given ID for duplicate asset
+    /// holders, duplicate destinations within an allocation, allocations whose
+    /// total overflows and guarantees that target the channel itself.
+    pub fn validate(&self, channel_id: &Bytes32) -> Vec<OutcomeIssue> {
+        let mut issues = Vec::new();
+
+        for (i, asset) in self.0.iter().enumerate() {
+            let asset_holder = checksum_address(asset.asset_holder_address().0.to_vec());
+
+            if self.0[..i]
+                .iter()
+                .any(|other| other.asset_holder_address() == asset.asset_holder_address())
+            {
+                issues.push(OutcomeIssue::DuplicateAssetHolder(asset_holder.clone()));
+            }
+
+            match asset {
+                AssetOutcome::AllocationAssetOutcome(allocation) => {
+                    let items = &allocation.allocation_items;
+                    for (j, item) in items.iter().enumerate() {
+                        if items[..j]
+                            .iter()
+                            .any(|other| other.destination == item.destination)
+                        {
+                            issues.push(OutcomeIssue::DuplicateDestination {
+                                asset_holder: asset_holder.clone(),
+                                destination: item.destination.to_vec().to_hex_string(),
+                            });
+                        }
+                    }
+
+                    if allocation.total().is_none() {
+                        issues.push(OutcomeIssue::TotalOverflow(asset_holder));
+                    }
+                }
+                AssetOutcome::GuaranteeAssetOutcome(guarantee) => {
+                    if &guarantee.guarantee.target_channel_id == channel_id {
+                        issues.push(OutcomeIssue::SelfGuarantee(asset_holder));
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+impl From<Vec<AssetOutcome>> for Outcome {
+    fn from(assets: Vec<AssetOutcome>) -> Self {
+        Self(assets)
+    }
+}
+
+impl<'a> IntoIterator for &'a Outcome {
+    type Item = &'a AssetOutcome;
+    type IntoIter = slice::Iter<'a, AssetOutcome>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.assets()
+    }
+}
+
+impl AssetOutcome {
+    pub fn asset_holder_address(&self) -> &Address {
+        match self {
+            Self::AllocationAssetOutcome(allocation) => &allocation.asset_holder_address,
+            Self::GuaranteeAssetOutcome(guarantee) => &guarantee.asset_holder_address,
+        }
+    }
+
+    pub fn as_allocation(&self) -> Option<&AllocationAssetOutcome> {
+        match self {
+            Self::AllocationAssetOutcome(allocation) => Some(allocation),
+            Self::GuaranteeAssetOutcome(_) => None,
+        }
+    }
+
+    pub fn as_guarantee(&self) -> Option<&GuaranteeAssetOutcome> {
+        match self {
+            Self::AllocationAssetOutcome(_) => None,
+            Self::GuaranteeAssetOutcome(guarantee) => Some(guarantee),
+        }
+    }
+}
+
+impl AllocationAssetOutcome {
+    pub fn items(&self) -> slice::Iter<'_, AllocationItem> {
+        self.allocation_items.iter()
+    }
+
+    /// The amount allocated to the first item for `destination`, if any.
+    pub fn amount_for(&self, destination: &Bytes32) -> Option<&Uint256> {
+        self.items()
+            .find(|item| &item.destination == destination)
+            .map(|item| &item.amount)
+    }
+
+    /// The sum of all amounts, or `None` if it overflows.
+    pub fn total(&self) -> Option<U256> {
+        self.items()
+            .try_fold(U256::zero(), |total, item| total.checked_add(item.amount.0))
+    }
+}
+
+impl<'a> IntoIterator for &'a AllocationAssetOutcome {
+    type Item = &'a AllocationItem;
+    type IntoIter = slice::Iter<'a, AllocationItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items()
+    }
+}
+
+/// Builds an `Outcome` asset by asset; see `Outcome::builder`.
+#[derive(Default)]
+pub struct OutcomeBuilder {
+    assets: Vec<AssetOutcome>,
+}
+
+impl OutcomeBuilder {
+    /// Adds an allocation for the given asset holder, to which items are
+    /// added with `AllocationBuilder::item`.
+    pub fn allocation(self, asset_holder_address: Address) -> AllocationBuilder {
+        AllocationBuilder {
+            outcome: self,
+            allocation: AllocationAssetOutcome {
+                asset_holder_address,
+                allocation_items: Vec::new(),
+            },
+        }
+    }
+
+    /// Adds a guarantee for the given asset holder.
+    pub fn guarantee(
+        mut self,
+        asset_holder_address: Address,
+        target_channel_id: Bytes32,
+        destinations: Vec<Bytes32>,
+    ) -> Self {
+        self.assets
+            .push(AssetOutcome::GuaranteeAssetOutcome(GuaranteeAssetOutcome {
+                asset_holder_address,
+                guarantee: Guarantee {
+                    target_channel_id,
+                    destinations,
+                },
+            }));
+        self
+    }
+
+    pub fn build(self) -> Outcome {
+        Outcome(self.assets)
+    }
+}
+
+/// Builds the allocation most recently added with `OutcomeBuilder::allocation`.
+pub struct AllocationBuilder {
+    outcome: OutcomeBuilder,
+    allocation: AllocationAssetOutcome,
+}
+
+impl AllocationBuilder {
+    pub fn item(mut self, destination: Bytes32, amount: impl Into<U256>) -> Self {
+        self.allocation.allocation_items.push(AllocationItem {
+            destination,
+            amount: Uint256(amount.into()),
+        });
+        self
+    }
+
+    /// Finishes this allocation and adds another one.
+    pub fn allocation(self, asset_holder_address: Address) -> AllocationBuilder {
+        self.finish().allocation(asset_holder_address)
+    }
+
+    /// Finishes this allocation and adds a guarantee.
+    pub fn guarantee(
+        self,
+        asset_holder_address: Address,
+        target_channel_id: Bytes32,
+        destinations: Vec<Bytes32>,
+    ) -> OutcomeBuilder {
+        self.finish()
+            .guarantee(asset_holder_address, target_channel_id, destinations)
+    }
+
+    pub fn build(self) -> Outcome {
+        self.finish().build()
+    }
+
+    fn finish(mut self) -> OutcomeBuilder {
+        self.outcome
+            .assets
+            .push(AssetOutcome::AllocationAssetOutcome(self.allocation));
+        self.outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn address(i: u64) -> Address {
+        Address::from_low_u64_be(i)
+    }
+
+    #[test]
+    fn builds_outcomes() {
+        let outcome = Outcome::builder()
+            .allocation(address(1))
+            .item(Bytes32::from_address(&address(0xa)), 5)
+            .item(Bytes32::from_address(&address(0xb)), U256::max_value())
+            .guarantee(
+                address(2),
+                Bytes32::from([0xff; 32]),
+                vec![Bytes32::from_address(&address(0xb))],
+            )
+            .allocation(address(3))
+            .build();
+
+        let expected: Outcome = serde_json::from_value(json!([
+            {
+                "assetHolderAddress": "0x0000000000000000000000000000000000000001",
+                "allocationItems": [
+                    {
+                        "destination": "0x000000000000000000000000000000000000000000000000000000000000000a",
+                        "amount": "5",
+                    },
+                    {
+                        "destination": "0x000000000000000000000000000000000000000000000000000000000000000b",
+                        "amount": format!("0x{:x}", U256::max_value()),
+                    },
+                ],
+            },
+            {
+                "assetHolderAddress": "0x0000000000000000000000000000000000000002",
+                "guarantee": {
+                    "targetChannelId": format!("0x{}", "ff".repeat(32)),
+                    "destinations": [
+                        "0x000000000000000000000000000000000000000000000000000000000000000b",
+                    ],
+                },
+            },
+            {
+                "assetHolderAddress": "0x0000000000000000000000000000000000000003",
+                "allocationItems": [],
+            },
+        ]))
+        .unwrap();
+
+        assert!(outcome == expected);
+    }
+
+    #[test]
+    fn validates_outcomes() {
+        let channel_id = Bytes32::from([0xcc; 32]);
+        let alice = Bytes32::from_address(&address(0xa));
+        let bob = Bytes32::from_address(&address(0xb));
+
+        let valid = Outcome::builder()
+            .allocation(address(1))
+            .item(alice.clone(), 5)
+            .item(bob.clone(), 3)
+            .guarantee(address(2), Bytes32::from([0xdd; 32]), vec![alice.clone()])
+            .build();
+        assert!(valid.validate(&channel_id).is_empty());
+
+        let invalid = Outcome::builder()
+            .allocation(address(1))
+            .item(alice.clone(), U256::max_value())
+            .item(bob.clone(), 3)
+            .item(alice.clone(), 1)
+            .guarantee(address(2), channel_id.clone(), vec![])
+            .allocation(address(1))
+            .build();
+        assert_eq!(
+            invalid.validate(&channel_id),
+            vec![
+                OutcomeIssue::DuplicateDestination {
+                    asset_holder: "0x0000000000000000000000000000000000000001".into(),
+                    destination: alice.to_vec().to_hex_string(),
+                },
+                OutcomeIssue::TotalOverflow("0x0000000000000000000000000000000000000001".into()),
+                OutcomeIssue::SelfGuarantee("0x0000000000000000000000000000000000000002".into()),
+                OutcomeIssue::DuplicateAssetHolder(
+                    "0x0000000000000000000000000000000000000001".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn accesses_assets_and_items() {
+        let outcome = Outcome::builder()
+            .allocation(address(1))
+            .item(Bytes32::from_address(&address(0xa)), 5)
+            .item(Bytes32::from_address(&address(0xb)), 3)
+            .guarantee(address(2), Bytes32::from([0xff; 32]), vec![])
+            .build();
+
+        assert_eq!(outcome.len(), 2);
+        assert_eq!(
+            outcome
+                .assets()
+                .map(AssetOutcome::asset_holder_address)
+                .collect::<Vec<_>>(),
+            vec![&address(1), &address(2)]
+        );
+        assert_eq!(outcome.allocations().count(), 1);
+        assert_eq!(outcome.guarantees().count(), 1);
+        assert!(outcome.asset(&address(2)).unwrap().as_guarantee().is_some());
+        assert!(outcome.asset(&address(3)).is_none());
+
+        let allocation = outcome.allocations().next().unwrap();
+        assert_eq!(allocation.items().count(), 2);
+        assert_eq!(allocation.total(), Some(U256::from(8)));
+        assert_eq!(
+            allocation
+                .amount_for(&Bytes32::from_address(&address(0xb)))
+                .map(|amount| amount.0),
+            Some(U256::from(3))
+        );
+        assert!(allocation
+            .amount_for(&Bytes32::from_address(&address(0xc)))
+            .is_none());
+    }
+}
@@ -0,0 +1,343 @@
+use std::cmp::min;
+
+use ethereum_types::U256;
+use serde_derive::*;
+
+use super::error::*;
+use super::state::*;
+use super::types::*;
+
+/// The result of paying out (part of) an allocation, as computed by the
+/// AssetHolder's `_computeNewAllocation`.
+#[derive(Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferPayouts {
+    /// The allocation with the paid out amounts deducted.
+    pub new_allocation: Vec<AllocationItem>,
+    /// The amount paid out for each of the given indices, or for every
+    /// allocation item if no indices were given.
+    pub payouts: Vec<Uint256>,
+    pub total_payouts: Uint256,
+    /// Whether every item of the new allocation is zero, in which case the
+    /// AssetHolder deletes the outcome.
+    pub safe_to_delete: bool,
+}
+
+impl AllocationAssetOutcome {
+    /// Computes what `transfer` (or, if `indices` is empty, `transferAll`)
+    /// pays out given the AssetHolder's `holdings` for the channel.
+    ///
+    /// Items are funded in order of priority, so when the channel is only
+    /// partially funded, items after the first underfunded one receive
+    /// nothing. `indices` must be strictly increasing.
+    pub fn compute_transfer_payouts(
+        &self,
+        holdings: &Uint256,
+        indices: &[usize],
+    ) -> Result<TransferPayouts> {
+        let allocation = &self.allocation_items;
+
+        for (i, index) in indices.iter().enumerate() {
+            if i > 0 && *index <= indices[i - 1] {
+                return Err(Error::IndicesNotIncreasing);
+            }
+            if *index >= allocation.len() {
+                return Err(Error::IndexOutOfRange {
+                    index: *index,
+                    length: allocation.len(),
+                });
+            }
+        }
+
+        let mut new_allocation = Vec::with_capacity(allocation.len());
+        let mut payouts = Vec::with_capacity(if indices.is_empty() {
+            allocation.len()
+        } else {
+            indices.len()
+        });
+        let mut total_payouts = U256::zero();
+        let mut surplus = holdings.0;
+        let mut k = 0;
+
+        for (i, item) in allocation.iter().enumerate() {
+            let affords = min(item.amount.0, surplus);
+
+            let amount = if indices.is_empty() || indices.get(k) == Some(&i) {
+                payouts.push(Uint256(affords));
+                total_payouts = total_payouts
+                    .checked_add(affords)
+                    .ok_or(Error::TotalAllocatedOverflow)?;
+                k += 1;
+                item.amount.0 - affords
+            } else {
+                item.amount.0
+            };
+
+            new_allocation.push(AllocationItem {
+                destination: item.destination.clone(),
+                amount: Uint256(amount),
+            });
+
+            // `affords` never exceeds the surplus, so this cannot underflow.
+            surplus -= affords;
+        }
+
+        let safe_to_delete = new_allocation.iter().all(|item| item.amount.0.is_zero());
+
+        Ok(TransferPayouts {
+            new_allocation,
+            payouts,
+            total_payouts: Uint256(total_payouts),
+            safe_to_delete,
+        })
+    }
+}
+
+/// The result of claiming a guarantee, as computed by the AssetHolder's
+/// `claimAll`.
+#[derive(Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimPayouts {
+    /// The target allocation with the paid out amounts deducted and the items
+    /// that were paid out in full removed.
+    pub new_allocation: Vec<AllocationItem>,
+    /// The amount paid out for each item of the target allocation.
+    pub payouts: Vec<Uint256>,
+    pub total_payouts: Uint256,
+    /// What is left of the guarantor's holdings.
+    pub remaining_holdings: Uint256,
+}
+
+impl Guarantee {
+    /// Computes what `claimAll` pays out of the guarantor's `holdings` for the
+    /// guarantee's `target` allocation.
+    ///
+    /// The guarantor first pays the target's items for each of its
+    /// `destinations` in turn, and only then the remaining items in the
+    /// target's own order.
+    pub fn compute_claim_payouts(
+        &self,
+        target: &AllocationAssetOutcome,
+        holdings: &Uint256,
+    ) -> ClaimPayouts {
+        let mut allocation: Vec<U256> = target
+            .allocation_items
+            .iter()
+            .map(|item| item.amount.0)
+            .collect();
+        let mut payouts = vec![U256::zero(); allocation.len()];
+        let mut balance = holdings.0;
+
+        for destination in &self.destinations {
+            if balance.is_zero() {
+                break;
+            }
+
+            // Like `claimAll`, pay only the first item for each destination,
+            // even if it has already been paid out.
+            let j = target
+                .allocation_items
+                .iter()
+                .position(|item| &item.destination == destination);
+            if let Some(j) = j {
+                pay(j, &mut allocation, &mut payouts, &mut balance);
+            }
+        }
+
+        for j in 0..allocation.len() {
+            if balance.is_zero() {
+                break;
+            }
+
+            pay(j, &mut allocation, &mut payouts, &mut balance);
+        }
+
+        let new_allocation = target
+            .allocation_items
+            .iter()
+            .zip(allocation)
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(item, amount)| AllocationItem {
+                destination: item.destination.clone(),
+                amount: Uint256(amount),
+            })
+            .collect();
+
+        ClaimPayouts {
+            new_allocation,
+            payouts: payouts.into_iter().map(Uint256).collect(),
+            total_payouts: Uint256(holdings.0 - balance),
+            remaining_holdings: Uint256(balance),
+        }
+    }
+}
+
+/// Pays as much of the `j`-th item of `allocation` as the `balance` affords.
+fn pay(j: usize, allocation: &mut [U256], payouts: &mut [U256], balance: &mut U256) {
+    let affords = min(allocation[j], *balance);
+    allocation[j] -= affords;
+    // The payouts for an item never exceed its original amount.
+    payouts[j] += affords;
+    *balance -= affords;
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn outcome(amounts: &[u64]) -> AllocationAssetOutcome {
+        serde_json::from_value(json!({
+            "assetHolderAddress": "0x0000000000000000000000000000000000000001",
+            "allocationItems": amounts
+                .iter()
+                .enumerate()
+                .map(|(i, amount)| json!({
+                    "destination": format!("0x{:064x}", i + 1),
+                    "amount": amount,
+                }))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    fn amounts(items: &[AllocationItem]) -> Vec<U256> {
+        items.iter().map(|item| item.amount.0).collect()
+    }
+
+    fn uints(values: &[Uint256]) -> Vec<U256> {
+        values.iter().map(|value| value.0).collect()
+    }
+
+    fn u256s(values: &[u64]) -> Vec<U256> {
+        values.iter().map(|value| U256::from(*value)).collect()
+    }
+
+    #[test]
+    fn transfers_all_when_fully_funded() {
+        let result = outcome(&[5, 3, 2])
+            .compute_transfer_payouts(&Uint256(10.into()), &[])
+            .unwrap();
+
+        assert_eq!(uints(&result.payouts), u256s(&[5, 3, 2]));
+        assert_eq!(amounts(&result.new_allocation), u256s(&[0, 0, 0]));
+        assert_eq!(result.total_payouts.0, U256::from(10));
+        assert!(result.safe_to_delete);
+    }
+
+    #[test]
+    fn pays_out_in_priority_order_when_partially_funded() {
+        let result = outcome(&[5, 3, 2])
+            .compute_transfer_payouts(&Uint256(6.into()), &[])
+            .unwrap();
+
+        assert_eq!(uints(&result.payouts), u256s(&[5, 1, 0]));
+        assert_eq!(amounts(&result.new_allocation), u256s(&[0, 2, 2]));
+        assert_eq!(result.total_payouts.0, U256::from(6));
+        assert!(!result.safe_to_delete);
+    }
+
+    #[test]
+    fn transfers_selected_indices() {
+        // Item 0 takes priority even though it is not paid out.
+        let result = outcome(&[5, 3, 0, 2])
+            .compute_transfer_payouts(&Uint256(9.into()), &[1, 2, 3])
+            .unwrap();
+
+        assert_eq!(uints(&result.payouts), u256s(&[3, 0, 1]));
+        assert_eq!(amounts(&result.new_allocation), u256s(&[5, 0, 0, 1]));
+        assert_eq!(result.total_payouts.0, U256::from(4));
+        assert!(!result.safe_to_delete);
+    }
+
+    #[test]
+    fn handles_large_amounts() {
+        let mut outcome = outcome(&[0, 0]);
+        outcome.allocation_items[0].amount = Uint256(U256::max_value());
+        outcome.allocation_items[1].amount = Uint256(U256::max_value());
+
+        let result = outcome
+            .compute_transfer_payouts(&Uint256(U256::max_value()), &[])
+            .unwrap();
+
+        assert_eq!(
+            uints(&result.payouts),
+            vec![U256::max_value(), U256::zero()]
+        );
+        assert_eq!(result.total_payouts.0, U256::max_value());
+    }
+
+    fn guarantee(destinations: &[usize]) -> Guarantee {
+        serde_json::from_value(json!({
+            "targetChannelId": format!("0x{:064x}", 0xff),
+            "destinations": destinations
+                .iter()
+                .map(|i| format!("0x{:064x}", i))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn claims_in_guarantee_priority_order() {
+        let result =
+            guarantee(&[3, 1]).compute_claim_payouts(&outcome(&[5, 3, 2]), &Uint256(6.into()));
+
+        assert_eq!(uints(&result.payouts), u256s(&[4, 0, 2]));
+        assert_eq!(amounts(&result.new_allocation), u256s(&[1, 3]));
+        assert!(
+            result.new_allocation[0].destination == outcome(&[5]).allocation_items[0].destination
+        );
+        assert_eq!(result.total_payouts.0, U256::from(6));
+        assert_eq!(result.remaining_holdings.0, U256::zero());
+    }
+
+    #[test]
+    fn claims_remaining_items_in_allocation_order() {
+        let result =
+            guarantee(&[2]).compute_claim_payouts(&outcome(&[5, 3, 2]), &Uint256(12.into()));
+
+        assert_eq!(uints(&result.payouts), u256s(&[5, 3, 2]));
+        assert!(result.new_allocation.is_empty());
+        assert_eq!(result.total_payouts.0, U256::from(10));
+        assert_eq!(result.remaining_holdings.0, U256::from(2));
+    }
+
+    #[test]
+    fn pays_only_first_item_and_skips_unknown_destinations() {
+        // Destination 2 appears twice in the target; the second listing of it
+        // in the guarantee pays nothing more since the first item has already
+        // been paid out. Destination 9 is not in the target at all.
+        let mut target = outcome(&[1, 2, 0, 4]);
+        target.allocation_items[3].destination = target.allocation_items[1].destination.clone();
+
+        let result = guarantee(&[9, 2, 2]).compute_claim_payouts(&target, &Uint256(5.into()));
+
+        assert_eq!(uints(&result.payouts), u256s(&[1, 2, 0, 2]));
+        assert_eq!(amounts(&result.new_allocation), u256s(&[2]));
+        assert_eq!(result.remaining_holdings.0, U256::zero());
+    }
+
+    #[test]
+    fn rejects_invalid_indices() {
+        let outcome = outcome(&[1, 2]);
+        let holdings = Uint256(3.into());
+
+        assert!(
+            outcome.compute_transfer_payouts(&holdings, &[1, 0]).err()
+                == Some(Error::IndicesNotIncreasing)
+        );
+        assert!(
+            outcome.compute_transfer_payouts(&holdings, &[1, 1]).err()
+                == Some(Error::IndicesNotIncreasing)
+        );
+        assert!(
+            outcome.compute_transfer_payouts(&holdings, &[2]).err()
+                == Some(Error::IndexOutOfRange {
+                    index: 2,
+                    length: 2
+                })
+        );
+    }
+}
@@ -1,9 +1,145 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+use ethereum_types::Address;
 use hex;
 use serde::ser::{Serialize, Serializer};
-use serde::de::{Error, Deserialize, Deserializer};
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{
+    DeserializeOwned, Error, Deserialize, Deserializer, IntoDeserializer, MapAccess, Visitor,
+};
+use serde_derive::Deserialize;
+use serde_json::Value;
 
-use super::state::RecoverableSignature;
+use super::error::{Error as NativeUtilsError, Result as NativeUtilsResult};
+use super::state::{RecoverableSignature, SplitSignature};
 use super::types::*;
+use super::utils::checksum_address;
+
+thread_local! {
+    static STRICT_CHECKSUMS: Cell<bool> = Cell::new(false);
+    static CHECKSUM_MISMATCH: RefCell<Option<(String, String)>> = RefCell::new(None);
+}
+
+/// Options for parsing inputs with `parse_value`.
+///
+/// `parse_value` hands the options to the address deserializers through
+/// thread-locals, so they only apply to values parsed with it. Deserializing
+/// with e.g. `serde_json::from_value` directly always uses the defaults.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseOptions {
+    /// Reject mixed-case addresses whose EIP-55 checksum is wrong. All-lowercase
+    /// and all-uppercase addresses carry no checksum and are always accepted.
+    #[serde(default)]
+    pub strict_checksums: bool,
+}
+
+/// Deserializes `value`, reporting errors along with the path of the offending
+/// field, prefixed with `name` (e.g. `state.channel.participants[1]`).
+pub fn parse_value<T: DeserializeOwned>(
+    value: Value,
+    name: &str,
+    options: &ParseOptions,
+) -> NativeUtilsResult<T> {
+    let strict = STRICT_CHECKSUMS.with(|strict| strict.replace(options.strict_checksums));
+    CHECKSUM_MISMATCH.with(|mismatch| mismatch.borrow_mut().take());
+
+    let result = serde_path_to_error::deserialize(value);
+
+    STRICT_CHECKSUMS.with(|cell| cell.set(strict));
+    let mismatch = CHECKSUM_MISMATCH.with(|mismatch| mismatch.borrow_mut().take());
+
+    result.map_err(|error| {
+        let path = match error.path().to_string() {
+            path if path == "." => name.to_string(),
+            path if path.starts_with('[') => format!("{}{}", name, path),
+            path => format!("{}.{}", name, path),
+        };
+        match mismatch {
+            Some((address, expected)) => NativeUtilsError::InvalidAddressChecksum {
+                path,
+                address,
+                expected,
+            },
+            None => NativeUtilsError::InvalidInput {
+                path,
+                message: error.into_inner().to_string(),
+            },
+        }
+    })
+}
+
+/// An address that, in strict mode, must carry a valid EIP-55 checksum if it
+/// is mixed-case.
+struct CheckedAddress(Address);
+
+impl<'de> Deserialize<'de> for CheckedAddress {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+        let unprefixed = match s.strip_prefix("0x") {
+            Some(unprefixed) if unprefixed.len() == 40 => unprefixed,
+            _ => return Err(D::Error::custom(format!("invalid address: {}", s))),
+        };
+        let bytes = hex::decode(unprefixed)
+            .map_err(|_| D::Error::custom(format!("invalid address: {}", s)))?;
+
+        let is_mixed_case = unprefixed.chars().any(|c| c.is_ascii_lowercase())
+            && unprefixed.chars().any(|c| c.is_ascii_uppercase());
+        if is_mixed_case && STRICT_CHECKSUMS.with(Cell::get) {
+            let expected = checksum_address(bytes.clone());
+            if expected != s {
+                let message = format!("invalid address checksum: expected {}", expected);
+                CHECKSUM_MISMATCH.with(|mismatch| mismatch.borrow_mut().replace((s, expected)));
+                return Err(D::Error::custom(message));
+            }
+        }
+
+        Ok(CheckedAddress(Address::from_slice(&bytes)))
+    }
+}
+
+/// Deserializes an address, verifying its checksum in strict mode.
+pub fn deserialize_address<'de, D>(deserializer: D) -> Result<Address, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    CheckedAddress::deserialize(deserializer).map(|address| address.0)
+}
+
+/// Deserializes a list of addresses, verifying their checksums in strict mode.
+pub fn deserialize_addresses<'de, D>(deserializer: D) -> Result<Vec<Address>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let addresses: Vec<CheckedAddress> = Deserialize::deserialize(deserializer)?;
+    Ok(addresses.into_iter().map(|address| address.0).collect())
+}
+
+/// Serializes an address as an EIP-55 checksummed hex string.
+pub fn serialize_address<S>(address: &Address, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(checksum_address(address.0.to_vec()).as_str())
+}
+
+/// Serializes a list of addresses as EIP-55 checksummed hex strings.
+pub fn serialize_addresses<S>(addresses: &[Address], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    use serde::ser::SerializeSeq;
+
+    let mut seq = serializer.serialize_seq(Some(addresses.len()))?;
+    for address in addresses {
+        seq.serialize_element(checksum_address(address.0.to_vec()).as_str())?;
+    }
+    seq.end()
+}
 
 impl Serialize for RecoverableSignature {
     fn serialize<S>(
@@ -29,12 +165,211 @@ impl Serialize for RecoverableSignature {
     }
 }
 
+struct RecoverableSignatureVisitor;
+
+impl<'de> Visitor<'de> for RecoverableSignatureVisitor {
+    type Value = RecoverableSignature;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex-encoded signature or an {r, s, v} object")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let bytes = Bytes::deserialize(s.into_deserializer())?;
+        RecoverableSignature::from_bytes(bytes).map_err(E::custom)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let split = SplitSignature::deserialize(MapAccessDeserializer::new(map))?;
+        RecoverableSignature::join(&split).map_err(A::Error::custom)
+    }
+}
+
 impl<'de> Deserialize<'de> for RecoverableSignature {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let bytes: Bytes = Deserialize::deserialize(deserializer)?;
-        Ok(RecoverableSignature::from_bytes(bytes).map_err(D::Error::custom)?)
+        deserializer.deserialize_any(RecoverableSignatureVisitor)
+    }
+}
+
+impl Serialize for NativeUtilsError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut error = serializer.serialize_struct("Error", 2)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::de::DeserializeOwned;
+    use serde::ser::Serialize;
+
+    use super::super::channel::Channel;
+    use super::super::state::*;
+    use super::super::types::*;
+
+    fn assert_round_trip<T>(json: &str)
+    where
+        T: DeserializeOwned + Serialize + PartialEq,
+    {
+        let value: T = serde_json::from_str(json).unwrap();
+        let serialized = serde_json::to_string(&value).unwrap();
+        let deserialized: T = serde_json::from_str(&serialized).unwrap();
+        assert!(deserialized == value, "{} did not round-trip", serialized);
+    }
+
+    const CHANNEL: &str = r#"{
+        "chainId": "1",
+        "channelNonce": "0x10",
+        "participants": [
+            "0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a",
+            "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1"
+        ]
+    }"#;
+
+    const ALLOCATION_ASSET_OUTCOME: &str = r#"{
+        "assetHolderAddress": "0x0000000000000000000000000000000000000000",
+        "allocationItems": [
+            {
+                "destination": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "amount": 5
+            },
+            {
+                "destination": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                "amount": "0x0de0b6b3a7640000"
+            }
+        ]
+    }"#;
+
+    const GUARANTEE_ASSET_OUTCOME: &str = r#"{
+        "assetHolderAddress": "0x90f8bf6a479f320ead074411a4b0e7944ea8c9c1",
+        "guarantee": {
+            "targetChannelId": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "destinations": [
+                "0x0000000000000000000000000000000000000000000000000000000000000002",
+                "0x0000000000000000000000000000000000000000000000000000000000000001"
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn primitives_round_trip() {
+        assert_round_trip::<Bytes>(r#""0x""#);
+        assert_round_trip::<Bytes>(r#""0xdeadbeef""#);
+        assert_round_trip::<Bytes32>(
+            r#""0x1111111111111111111111111111111111111111111111111111111111111111""#,
+        );
+        assert_round_trip::<Uint48>("0");
+        assert_round_trip::<Uint48>("281474976710655");
+        assert_round_trip::<Uint256>("0");
+        assert_round_trip::<Uint256>(r#""1000000000000000000""#);
+        assert_round_trip::<Uint256>(
+            r#""0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff""#,
+        );
+    }
+
+    #[test]
+    fn outcomes_round_trip() {
+        assert_round_trip::<AllocationAssetOutcome>(ALLOCATION_ASSET_OUTCOME);
+        assert_round_trip::<GuaranteeAssetOutcome>(GUARANTEE_ASSET_OUTCOME);
+        assert_round_trip::<AssetOutcome>(ALLOCATION_ASSET_OUTCOME);
+        assert_round_trip::<AssetOutcome>(GUARANTEE_ASSET_OUTCOME);
+        assert_round_trip::<Outcome>("[]");
+        assert_round_trip::<Outcome>(&format!(
+            "[{}, {}]",
+            ALLOCATION_ASSET_OUTCOME, GUARANTEE_ASSET_OUTCOME
+        ));
+    }
+
+    #[test]
+    fn channels_and_states_round_trip() {
+        assert_round_trip::<Channel>(CHANNEL);
+        assert_round_trip::<State>(&format!(
+            r#"{{
+                "turnNum": 5,
+                "isFinal": false,
+                "channel": {},
+                "challengeDuration": 100,
+                "outcome": [{}],
+                "appDefinition": "0x90f8bf6a479f320ead074411a4b0e7944ea8c9c1",
+                "appData": "0x00"
+            }}"#,
+            CHANNEL, ALLOCATION_ASSET_OUTCOME
+        ));
+    }
+
+    #[test]
+    fn serializes_nitro_json_shape() {
+        let channel: Channel = serde_json::from_str(CHANNEL).unwrap();
+        assert_eq!(
+            serde_json::to_string(&channel).unwrap(),
+            r#"{"chainId":"0x01","channelNonce":"0x10","participants":["0x19E7E376E7C213B7E7e7e46cc70A5dD086DAff2A","0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1"]}"#
+        );
+    }
+
+    #[test]
+    fn strict_checksums() {
+        use super::super::error::Error;
+        use super::{parse_value, ParseOptions};
+
+        let strict = ParseOptions {
+            strict_checksums: true,
+        };
+        let mut channel: serde_json::Value = serde_json::from_str(CHANNEL).unwrap();
+
+        // Lowercase and correctly checksummed addresses are accepted.
+        assert!(parse_value::<Channel>(channel.clone(), "channel", &strict).is_ok());
+
+        channel["participants"][1] = "0x90f8bf6A479f320ead074411a4B0e7944Ea8c9C1".into();
+        assert!(parse_value::<Channel>(channel.clone(), "channel", &Default::default()).is_ok());
+        assert_eq!(
+            parse_value::<Channel>(channel, "channel", &strict).err(),
+            Some(Error::InvalidAddressChecksum {
+                path: "channel.participants[1]".into(),
+                address: "0x90f8bf6A479f320ead074411a4B0e7944Ea8c9C1".into(),
+                expected: "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1".into(),
+            })
+        );
+
+        let outcome = ALLOCATION_ASSET_OUTCOME.replace(
+            "0x0000000000000000000000000000000000000000",
+            "0x90f8bf6A479f320ead074411a4B0e7944Ea8c9C1",
+        );
+        let outcome: serde_json::Value = serde_json::from_str(&format!("[{}]", outcome)).unwrap();
+        assert!(matches!(
+            parse_value::<Outcome>(outcome, "outcome", &strict),
+            Err(Error::InvalidAddressChecksum { path, .. }) if path == "outcome[0].assetHolderAddress"
+        ));
+    }
+
+    #[test]
+    fn parse_errors_have_paths() {
+        use super::super::error::Error;
+        use super::parse_value;
+
+        let mut channel: serde_json::Value = serde_json::from_str(CHANNEL).unwrap();
+        channel["participants"][0] = "0x1234".into();
+        assert_eq!(
+            parse_value::<Channel>(channel, "channel", &Default::default()).err(),
+            Some(Error::InvalidInput {
+                path: "channel.participants[0]".into(),
+                message: "invalid address: 0x1234".into(),
+            })
+        );
     }
 }
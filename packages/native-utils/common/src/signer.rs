@@ -0,0 +1,78 @@
+use ethereum_types::Address;
+use secp256k1::{PublicKey, SecretKey};
+use zeroize::Zeroize;
+
+use super::error::*;
+use super::state::*;
+use super::types::*;
+use super::utils::*;
+
+/// A parsed private key together with its address, so that signing many
+/// states does not have to parse (or pass around) the raw key every time.
+///
+/// The secret key is cleared from memory when the signer is dropped, as
+/// libsecp256k1's `SecretKey` zeroes its scalar on drop.
+pub struct Signer {
+    secret_key: SecretKey,
+    address: Address,
+}
+
+impl Signer {
+    /// Parses the given private key, zeroizing the passed-in bytes afterwards.
+    pub fn new(mut private_key: Bytes) -> Result<Self> {
+        let secret_key = parse_private_key(&private_key);
+        private_key.0.zeroize();
+
+        Ok(Self::from_secret_key(secret_key?))
+    }
+
+    pub fn from_secret_key(secret_key: SecretKey) -> Self {
+        let public_key = PublicKey::from_secret_key(&secret_key);
+        let address = Address::from_slice(&public_key_to_address(public_key));
+
+        Self {
+            secret_key,
+            address,
+        }
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn checksummed_address(&self) -> String {
+        checksum_address(self.address.as_bytes().to_vec())
+    }
+
+    pub fn sign_state(&self, state: &State) -> StateSignature {
+        state.sign_with_secret_key(&self.secret_key)
+    }
+
+    pub fn sign_message(&self, message: &[u8]) -> RecoverableSignature {
+        sign_message(message, &self.secret_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{private_key, state, ADDRESS2, PRIVATE_KEY2};
+    use super::*;
+
+    #[test]
+    fn signs_like_state_sign() {
+        let state = state(1);
+
+        let signer = Signer::new(private_key(PRIVATE_KEY2)).unwrap();
+        assert_eq!(signer.checksummed_address(), ADDRESS2);
+
+        let signed = signer.sign_state(&state);
+        let expected = state.sign(private_key(PRIVATE_KEY2)).unwrap();
+        assert!(signed.hash == expected.hash);
+        assert!(signed.signature.as_bytes() == expected.signature.as_bytes());
+    }
+
+    #[test]
+    fn rejects_invalid_private_key() {
+        assert!(Signer::new(private_key("0x00")).err() == Some(Error::InvalidPrivateKey));
+    }
+}
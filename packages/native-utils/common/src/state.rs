@@ -5,13 +5,16 @@ use ethereum_types::{Address, U256};
 use secp256k1::{recover, sign, Message, RecoveryId, SecretKey, Signature};
 use serde_derive::*;
 
+use super::app::*;
 use super::encode::*;
+use super::error::*;
+use super::serde::*;
 use super::tokenize::*;
 use super::types::*;
 use super::utils::*;
 use super::channel::*;
 
-#[derive(Deserialize,PartialEq)]
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct AllocationItem {
     pub destination: Bytes32,
@@ -24,6 +27,20 @@ impl Tokenize for AllocationItem {
     }
 }
 
+impl Detokenize for AllocationItem {
+    fn param_type() -> ParamType {
+        <(Bytes32, Uint256)>::param_type()
+    }
+
+    fn detokenize(token: Token) -> Result<Self> {
+        let (destination, amount) = Detokenize::detokenize(token)?;
+        Ok(AllocationItem {
+            destination,
+            amount,
+        })
+    }
+}
+
 #[repr(u8)]
 enum AssetOutcomeType {
     AllocationOutcomeType = 0,
@@ -39,9 +56,10 @@ impl Tokenize for AssetOutcomeType {
     }
 }
 
-#[derive(Deserialize,PartialEq)]
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct AllocationAssetOutcome {
+    #[serde(serialize_with = "serialize_address", deserialize_with = "deserialize_address")]
     pub asset_holder_address: Address,
     pub allocation_items: Vec<AllocationItem>,
 }
@@ -55,7 +73,7 @@ impl Tokenize for AllocationAssetOutcome {
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Guarantee {
     pub target_channel_id: Bytes32,
@@ -71,9 +89,24 @@ impl Tokenize for Guarantee {
     }
 }
 
-#[derive(Deserialize,PartialEq)]
+impl Detokenize for Guarantee {
+    fn param_type() -> ParamType {
+        <(Bytes32, Vec<Bytes32>)>::param_type()
+    }
+
+    fn detokenize(token: Token) -> Result<Self> {
+        let (target_channel_id, destinations) = Detokenize::detokenize(token)?;
+        Ok(Guarantee {
+            target_channel_id,
+            destinations,
+        })
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GuaranteeAssetOutcome {
+    #[serde(serialize_with = "serialize_address", deserialize_with = "deserialize_address")]
     pub asset_holder_address: Address,
     pub guarantee: Guarantee,
 }
@@ -87,13 +120,47 @@ impl Tokenize for GuaranteeAssetOutcome {
     }
 }
 
-#[derive(Deserialize, PartialEq)]
+#[derive(Clone, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum AssetOutcome {
     AllocationAssetOutcome(AllocationAssetOutcome),
     GuaranteeAssetOutcome(GuaranteeAssetOutcome),
 }
 
+impl<'de> serde::Deserialize<'de> for AssetOutcome {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        // Rather than `#[serde(untagged)]`, which discards the errors (and
+        // their paths) of the individual variants, pick the variant by the
+        // fields that are present.
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RawAssetOutcome {
+            #[serde(deserialize_with = "deserialize_address")]
+            asset_holder_address: Address,
+            allocation_items: Option<Vec<AllocationItem>>,
+            guarantee: Option<Guarantee>,
+        }
+
+        let raw = RawAssetOutcome::deserialize(deserializer)?;
+        match (raw.allocation_items, raw.guarantee) {
+            (Some(allocation_items), _) => Ok(Self::AllocationAssetOutcome(AllocationAssetOutcome {
+                asset_holder_address: raw.asset_holder_address,
+                allocation_items,
+            })),
+            (None, Some(guarantee)) => Ok(Self::GuaranteeAssetOutcome(GuaranteeAssetOutcome {
+                asset_holder_address: raw.asset_holder_address,
+                guarantee,
+            })),
+            (None, None) => Err(D::Error::custom("expected `allocationItems` or `guarantee`")),
+        }
+    }
+}
+
 impl Tokenize for AssetOutcome {
     fn tokenize(&self) -> Token {
         let (asset_holder_address, encoded) = match self {
@@ -107,9 +174,34 @@ impl Tokenize for AssetOutcome {
     }
 }
 
-#[derive(Deserialize, PartialEq)]
+impl Detokenize for AssetOutcome {
+    fn param_type() -> ParamType {
+        <(Address, Bytes)>::param_type()
+    }
+
+    fn detokenize(token: Token) -> Result<Self> {
+        let (asset_holder_address, encoded): (Address, Bytes) = Detokenize::detokenize(token)?;
+        let (outcome_type, data): (Uint256, Bytes) = Decode::decode(&encoded)?;
+
+        if outcome_type.0 == U256::from(AssetOutcomeType::AllocationOutcomeType as u8) {
+            Ok(Self::AllocationAssetOutcome(AllocationAssetOutcome {
+                asset_holder_address,
+                allocation_items: Decode::decode(&data)?,
+            }))
+        } else if outcome_type.0 == U256::from(AssetOutcomeType::GuaranteeOutcomeType as u8) {
+            Ok(Self::GuaranteeAssetOutcome(GuaranteeAssetOutcome {
+                asset_holder_address,
+                guarantee: Decode::decode(&data)?,
+            }))
+        } else {
+            Err(Error::UnknownAssetOutcomeType(outcome_type.0))
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(transparent)]
-pub struct Outcome(Vec<AssetOutcome>);
+pub struct Outcome(pub(crate) Vec<AssetOutcome>);
 
 impl Outcome {
     pub fn hash(&self) -> Bytes32 {
@@ -123,7 +215,17 @@ impl Tokenize for Outcome {
     }
 }
 
-#[derive(Deserialize)]
+impl Detokenize for Outcome {
+    fn param_type() -> ParamType {
+        Vec::<AssetOutcome>::param_type()
+    }
+
+    fn detokenize(token: Token) -> Result<Self> {
+        Detokenize::detokenize(token).map(Outcome)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct State {
     pub turn_num: Uint48,
@@ -131,11 +233,31 @@ pub struct State {
     pub channel: Channel,
     pub challenge_duration: Uint48,
     pub outcome: Outcome,
+    #[serde(serialize_with = "serialize_address", deserialize_with = "deserialize_address")]
     pub app_definition: Address,
     pub app_data: Bytes,
 }
 
-#[derive(Serialize)]
+/// Opt-in checks for `State::validate_peer_update_with_options`.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationOptions {
+    /// Reject peer updates whose outcome has issues found by `Outcome::validate`.
+    #[serde(default)]
+    pub validate_outcome: bool,
+}
+
+/// The options of `validatePeerUpdate` in the bindings: how to parse the
+/// states, and which opt-in checks to run on the peer update.
+#[derive(Clone, Copy, Default, Deserialize)]
+pub struct PeerUpdateOptions {
+    #[serde(flatten)]
+    pub parse: ParseOptions,
+    #[serde(flatten)]
+    pub validation: ValidationOptions,
+}
+
+#[derive(Serialize, PartialEq)]
 pub enum Status {
     True,
     NeedToCheckApp,
@@ -168,123 +290,526 @@ impl State {
         .into()
     }
 
-    pub fn sign(self, private_key: Bytes) -> Result<StateSignature, &'static str> {
-        let hash = self.hash();
-        let hashed_message = hash_message(&hash);
-        let message = Message::parse(&hashed_message);
-        let secret_key =
-            SecretKey::parse_slice(private_key.deref()).or_else(|_| Err("invalid private key"))?;
-        let (mut signature, recovery_id) = sign(&message, &secret_key);
+    pub fn sign(self, private_key: Bytes) -> Result<StateSignature> {
+        let secret_key = parse_private_key(&private_key)?;
+        Ok(self.sign_with_secret_key(&secret_key))
+    }
 
-        signature.normalize_s();
+    pub fn sign_with_secret_key(&self, secret_key: &SecretKey) -> StateSignature {
+        let hash = self.hash();
+        let signature = sign_message(&hash, secret_key);
 
-        Ok(StateSignature {
-            hash,
-            signature: RecoverableSignature(signature, recovery_id),
-        })
+        StateSignature { hash, signature }
     }
 
-    pub fn recover_address(&self, signature: Bytes) -> Result<String, &'static str> {
-        let hash = self.hash();
-        let hashed_message = hash_message(&hash);
-        let message = Message::parse(&hashed_message);
-        let parsed_signature = Signature::parse_slice(&signature[0..signature.len() - 1])
-            .or_else(|_| Err("invalid signature length"))?;
-        let recovery_id = RecoveryId::parse_rpc(signature[signature.len() - 1])
-            .or_else(|_| Err("invalid recovery ID"))?;
-        let public_key = recover(&message, &parsed_signature, &recovery_id)
-            .or_else(|_| Err("invalid signature"))?;
+    pub fn recover_address(&self, signature: Bytes) -> Result<String> {
+        recover_signer(&self.hash(), &signature)
+    }
 
-        Ok(checksum_address(public_key_to_address(public_key)))
+    pub fn validate_peer_update(&self, peer_update: State, peer_signature: Bytes) -> Result<Status> {
+        self.validate_peer_update_with_options(peer_update, peer_signature, &Default::default())
     }
 
-    pub fn validate_peer_update(&self, peer_update: State, peer_signature: Bytes) -> Result<Status,  &'static str> {
+    pub fn validate_peer_update_with_options(
+        &self,
+        peer_update: State,
+        peer_signature: Bytes,
+        options: &ValidationOptions,
+    ) -> Result<Status> {
         peer_update.validate_signature(peer_signature)?;
-        self.require_valid_protocol_transition(peer_update)
+        if options.validate_outcome {
+            let issues = peer_update.outcome.validate(&peer_update.channel.id());
+            if !issues.is_empty() {
+                return Err(Error::InvalidOutcome(issues));
+            }
+        }
+        self.require_valid_protocol_transition(&peer_update)
+    }
+
+    /// The index of the participant who moves in this state's turn, i.e. whose
+    /// signature `validate_peer_update` expects, following ForceMove's rule
+    /// that participant `turnNum % n` owns turn `turnNum`.
+    pub(crate) fn mover_index(&self) -> Result<usize> {
+        let num_participants = self.channel.participants.len() as u64;
+        let mover_index = self
+            .turn_num
+            .0
+            .checked_rem(num_participants)
+            .ok_or(Error::NoParticipants)?;
+
+        Ok(mover_index as usize)
     }
 
-    fn validate_signature(&self, signature: Bytes) -> Result<(),  &'static str> {
-        let signer_index = ((self.turn_num.0 -1) % self.channel.participants.len() as u64) as usize;
-        let signer_address = self.channel.participants[signer_index];
+    fn validate_signature(&self, signature: Bytes) -> Result<()> {
+        let signer_index = self.mover_index()?;
+        let signer_address = checksum_address(self.channel.participants[signer_index].0.to_vec());
         let recovered_address = self.recover_address(signature)?;
 
-        if recovered_address.eq(&checksum_address(signer_address.0.to_vec())) {
+        if recovered_address.eq(&signer_address) {
             Ok(())
         } else {
-            Err("Signature verification failed")
+            Err(Error::SignerMismatch {
+                expected: signer_address,
+                recovered: recovered_address,
+            })
         }
     }
 
-    fn _require_extra_implicit_checks(&self, to_state: &State) -> Result<(), &'static str> {
+    fn _require_extra_implicit_checks(&self, to_state: &State) -> Result<()> {
         if &self.turn_num.0 + 1 != to_state.turn_num.0 {
-            Err("turnNum must increment by one")
+            Err(Error::TurnNumNotIncremented {
+                expected: self.turn_num.0 + 1,
+                actual: to_state.turn_num.0,
+            })
         } else if self.channel.chain_id != to_state.channel.chain_id {
-            Err("chainId must not change")
+            Err(Error::ChainIdChanged)
         } else if self.channel.channel_nonce != to_state.channel.channel_nonce {
-            Err("channelNonce must not change")
+            Err(Error::ChannelNonceChanged)
         } else if self.app_definition != to_state.app_definition {
-            Err("appDefinition must not change")
+            Err(Error::AppDefinitionChanged {
+                from: checksum_address(self.app_definition.0.to_vec()),
+                to: checksum_address(to_state.app_definition.0.to_vec()),
+            })
         } else if self.challenge_duration != to_state.challenge_duration {
-            Err("challengeDuration must not change")
+            Err(Error::ChallengeDurationChanged {
+                from: self.challenge_duration.0,
+                to: to_state.challenge_duration.0,
+            })
         } else {
             Ok(())
         }
     }
 
-    pub fn require_valid_protocol_transition(&self, to_state: State) -> Result<Status, &'static str> {
-        self._require_extra_implicit_checks(&to_state)?;
+    pub fn require_valid_protocol_transition(&self, to_state: &State) -> Result<Status> {
+        self._require_extra_implicit_checks(to_state)?;
 
         if to_state.is_final {
             if self.outcome != to_state.outcome {
-                Err("Outcome change forbidden")
+                Err(Error::OutcomeChanged)
             } else {
                 Ok(Status::True)
             }
         } else {
             if self.is_final {
-                Err("transition from a final state to a non-final state")
+                Err(Error::FinalToNonFinal)
             } else {
                 if to_state.turn_num < Uint48(2 * to_state.channel.participants.len() as u64) {
                     if self.outcome != to_state.outcome {
-                        Err("Outcome change forbidden")
+                        Err(Error::OutcomeChanged)
                     } else if self.app_data != to_state.app_data {
-                        Err("appData change forbidden")
+                        Err(Error::AppDataChanged)
                     }
                     else {
                         Ok(Status::True)
                     }
                 }
                 else {
-                    Ok(Status::NeedToCheckApp)
+                    match validate_app_transition(self, to_state) {
+                        Some(result) => result.map(|_| Status::True),
+                        None => Ok(Status::NeedToCheckApp),
+                    }
                 }
             }
         }
     }
 }
 
+pub fn parse_private_key(private_key: &Bytes) -> Result<SecretKey> {
+    SecretKey::parse_slice(private_key.deref()).or(Err(Error::InvalidPrivateKey))
+}
+
+pub fn recover_signer(hash: &Bytes32, signature: &Bytes) -> Result<String> {
+    recover_message_signer(hash, signature)
+}
+
+/// Signs an arbitrary message the way `personal_sign` does, i.e. signs the
+/// hash of the message prefixed with `\x19Ethereum Signed Message:\n<length>`
+/// as specified in EIP-191.
+pub fn sign_message(message: &[u8], secret_key: &SecretKey) -> RecoverableSignature {
+    sign_digest(&hash_message(message), secret_key)
+}
+
+/// Recovers the checksummed address that signed a message with `sign_message`.
+pub fn recover_message_signer(message: &[u8], signature: &Bytes) -> Result<String> {
+    recover_digest_signer(&hash_message(message), signature)
+}
+
+pub(crate) fn sign_digest(digest: &[u8; 32], secret_key: &SecretKey) -> RecoverableSignature {
+    let message = Message::parse(digest);
+    let (mut signature, recovery_id) = sign(&message, secret_key);
+
+    signature.normalize_s();
+
+    RecoverableSignature(signature, recovery_id)
+}
+
+pub(crate) fn recover_digest_signer(digest: &[u8; 32], signature: &Bytes) -> Result<String> {
+    let RecoverableSignature(signature, recovery_id) = RecoverableSignature::from_slice(signature)?;
+    let message = Message::parse(digest);
+    let public_key = recover(&message, &signature, &recovery_id)
+        .or(Err(Error::InvalidSignature))?;
+
+    Ok(checksum_address(public_key_to_address(public_key)))
+}
+
+pub fn verify_signature(hash: Bytes32, address: Address, signature: Bytes) -> Result<bool> {
+    let recovered_address = recover_signer(&hash, &signature)?;
+    Ok(recovered_address == checksum_address(address.0.to_vec()))
+}
+
 pub struct RecoverableSignature(pub Signature, pub RecoveryId);
 
 impl RecoverableSignature {
     pub fn as_bytes(self) -> Bytes {
         let mut v = self.0.serialize().to_vec();
-        v.push(self.1.serialize());
+        v.push(self.1.serialize() + 27);
         Bytes(v)
     }
 
-    pub fn from_bytes(bytes: Bytes) -> Result<RecoverableSignature,  &'static str> {
-        assert_eq!(65,bytes.0.len());
-        let mut a: [u8; 64] = [0; 64];
-        a.copy_from_slice(&bytes.0[0..64]);
+    pub fn from_bytes(bytes: Bytes) -> Result<RecoverableSignature> {
+        Self::from_slice(&bytes)
+    }
+
+    /// Parses a 65-byte `r || s || v` signature or a 64-byte EIP-2098 compact
+    /// signature. `v` may be a raw recovery ID (0 or 1), as used by Ethereum
+    /// (27 or 28), or as in EIP-155 transactions (`chainId * 2 + 35` or
+    /// `chainId * 2 + 36`).
+    pub fn from_slice(bytes: &[u8]) -> Result<RecoverableSignature> {
+        if bytes.len() == 64 {
+            return Self::from_compact(bytes);
+        }
+        if bytes.len() != 65 {
+            return Err(Error::InvalidSignatureLength(bytes.len()));
+        }
+
+        let mut rs = [0; 64];
+        rs.copy_from_slice(&bytes[0..64]);
+        Ok(RecoverableSignature(
+            Signature::parse(&rs),
+            parse_recovery_id(bytes[64])?,
+        ))
+    }
+
+    /// Splits the signature into the `{r, s, v}` form that the nitro-protocol
+    /// contracts take, with `v` being 27 or 28.
+    pub fn split(&self) -> SplitSignature {
+        let bytes = self.0.serialize();
+        let mut r = [0; 32];
+        let mut s = [0; 32];
+        r.copy_from_slice(&bytes[0..32]);
+        s.copy_from_slice(&bytes[32..64]);
+        SplitSignature {
+            r: r.into(),
+            s: s.into(),
+            v: self.1.serialize() + 27,
+        }
+    }
+
+    /// Joins a signature split with `split`, accepting the same values of `v`
+    /// as `from_slice`.
+    pub fn join(split: &SplitSignature) -> Result<RecoverableSignature> {
+        let mut bytes = Vec::with_capacity(65);
+        bytes.extend_from_slice(&split.r);
+        bytes.extend_from_slice(&split.s);
+        bytes.push(split.v);
+        Self::from_slice(&bytes)
+    }
+
+    /// Encodes the signature in the 64-byte EIP-2098 form `r || yParityAndS`,
+    /// where the top bit of `s` holds the recovery ID.
+    pub fn to_compact(&self) -> Bytes {
+        let mut signature = self.0.clone();
+        let mut y_parity = self.1.serialize();
+        // A low `s` leaves the top bit free. `s` and `n - s` are both valid for
+        // the same message and key, so swap a high `s` for its low counterpart,
+        // which flips the recovery ID.
+        if signature.s.is_high() {
+            signature.normalize_s();
+            y_parity ^= 1;
+        }
+
+        let mut bytes = signature.serialize();
+        bytes[32] |= y_parity << 7;
+        Bytes(bytes.to_vec())
+    }
+
+    /// Parses a 64-byte EIP-2098 compact signature.
+    pub fn from_compact(bytes: &[u8]) -> Result<RecoverableSignature> {
+        if bytes.len() != 64 {
+            return Err(Error::InvalidSignatureLength(bytes.len()));
+        }
+
+        let mut rs = [0; 64];
+        rs.copy_from_slice(bytes);
+        let y_parity = rs[32] >> 7;
+        rs[32] &= 0x7f;
         Ok(RecoverableSignature(
-            Signature::parse(&a),
-            RecoveryId::parse(bytes.0[64] - 27).map_err(|_| "Invalid recovery ID")?
+            Signature::parse(&rs),
+            parse_recovery_id(y_parity)?,
         ))
     }
 }
 
+/// A signature in the `{r, s, v}` form, as taken by the nitro-protocol
+/// contracts and returned by ethers' `splitSignature`.
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
+pub struct SplitSignature {
+    pub r: Bytes32,
+    pub s: Bytes32,
+    pub v: u8,
+}
+
+fn parse_recovery_id(v: u8) -> Result<RecoveryId> {
+    let id = match v {
+        0 | 1 => v,
+        27 | 28 => v - 27,
+        35..=255 => (v - 35) % 2,
+        _ => return Err(Error::InvalidRecoveryId(v)),
+    };
+    RecoveryId::parse(id).or(Err(Error::InvalidRecoveryId(v)))
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StateSignature {
-    hash: Bytes32,
-    signature: RecoverableSignature,
+    pub hash: Bytes32,
+    pub signature: RecoverableSignature,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::fixtures::{self, address, private_key, ADDRESS1, PRIVATE_KEY1, PRIVATE_KEY2};
+    use super::*;
+
+    fn signature_with_v(v: u8) -> Bytes {
+        let secret_key = parse_private_key(&private_key(PRIVATE_KEY1)).unwrap();
+        let mut signature = sign_message(b"hello", &secret_key).as_bytes();
+        let recovery_id = signature.0[64] - 27;
+        signature.0[64] = v + recovery_id;
+        signature
+    }
+
+    /// A xorshift generator, so that the randomized tests are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn bytes(&mut self, len: usize) -> Vec<u8> {
+            (0..len).map(|_| self.next() as u8).collect()
+        }
+    }
+
+    #[test]
+    fn accepts_all_v_conventions() {
+        // Raw recovery IDs, Ethereum's 27/28 and EIP-155 with chain IDs 1 and 109.
+        for v in &[0, 27, 37, 253] {
+            let signature = signature_with_v(*v);
+            assert_eq!(recover_message_signer(b"hello", &signature), Ok(ADDRESS1.into()));
+        }
+    }
+
+    #[test]
+    fn rejects_other_v_values() {
+        for v in &[2, 26, 29, 30, 34] {
+            let mut signature = signature_with_v(0);
+            signature.0[64] = *v;
+            assert_eq!(
+                recover_message_signer(b"hello", &signature),
+                Err(Error::InvalidRecoveryId(*v))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_lengths() {
+        for len in &[0, 1, 63, 66] {
+            assert_eq!(
+                recover_message_signer(b"hello", &Bytes(vec![27; *len])),
+                Err(Error::InvalidSignatureLength(*len))
+            );
+            assert!(RecoverableSignature::from_slice(&vec![27; *len]).is_err());
+        }
+    }
+
+    #[test]
+    fn round_trips_compact_signatures() {
+        let secret_key = parse_private_key(&private_key(PRIVATE_KEY1)).unwrap();
+
+        // Enough messages to cover both recovery IDs.
+        for message in &["a", "b", "c", "d", "e", "f"] {
+            let signature = sign_message(message.as_bytes(), &secret_key).as_bytes();
+            let compact = RecoverableSignature::from_slice(&signature).unwrap().to_compact();
+            assert_eq!(compact.len(), 64);
+            assert_eq!(
+                recover_message_signer(message.as_bytes(), &compact),
+                Ok(ADDRESS1.into())
+            );
+
+            let expanded = RecoverableSignature::from_compact(&compact).unwrap().as_bytes();
+            assert!(expanded == signature);
+        }
+    }
+
+    #[test]
+    fn normalizes_high_s_compact_signatures() {
+        let secret_key = parse_private_key(&private_key(PRIVATE_KEY1)).unwrap();
+
+        for message in &["a", "b", "c", "d", "e", "f"] {
+            let RecoverableSignature(low_s, recovery_id) =
+                sign_message(message.as_bytes(), &secret_key);
+            let high_s = RecoverableSignature(
+                Signature {
+                    r: low_s.r.clone(),
+                    s: -low_s.s.clone(),
+                },
+                RecoveryId::parse(recovery_id.serialize() ^ 1).unwrap(),
+            );
+            let low_s = RecoverableSignature(low_s, recovery_id).as_bytes();
+            let high_s = high_s.as_bytes();
+            assert_eq!(
+                recover_message_signer(message.as_bytes(), &high_s),
+                Ok(ADDRESS1.into())
+            );
+
+            let compact = RecoverableSignature::from_slice(&high_s).unwrap().to_compact();
+            assert_eq!(
+                recover_message_signer(message.as_bytes(), &compact),
+                Ok(ADDRESS1.into())
+            );
+
+            let expanded = RecoverableSignature::from_compact(&compact).unwrap().as_bytes();
+            assert!(expanded == low_s);
+        }
+    }
+
+    #[test]
+    fn encodes_eip2098_example() {
+        // The example from EIP-2098.
+        let signature = hex::decode(concat!(
+            "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+            "7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+            "1c",
+        ))
+        .unwrap();
+        let compact = RecoverableSignature::from_slice(&signature).unwrap().to_compact();
+        assert_eq!(
+            hex::encode(&compact.0),
+            concat!(
+                "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+                "fe865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+            )
+        );
+    }
+
+    #[test]
+    fn splits_and_joins_signatures() {
+        let signature = signature_with_v(27);
+        let split = RecoverableSignature::from_slice(&signature).unwrap().split();
+        assert!(split.r.to_vec() == signature[0..32].to_vec());
+        assert!(split.s.to_vec() == signature[32..64].to_vec());
+        assert_eq!(split.v, signature[64]);
+
+        let joined = RecoverableSignature::join(&split).unwrap().as_bytes();
+        assert!(joined == signature);
+
+        // Both forms deserialize to the same signature.
+        let from_object: RecoverableSignature =
+            serde_json::from_value(serde_json::to_value(&split).unwrap()).unwrap();
+        assert!(from_object.as_bytes() == signature);
+
+        let invalid = SplitSignature { v: 29, ..split };
+        assert_eq!(
+            RecoverableSignature::join(&invalid).err(),
+            Some(Error::InvalidRecoveryId(29))
+        );
+    }
+
+    fn peer_update(turn_num: u64, participants: &[&str]) -> State {
+        let mut state = fixtures::state(turn_num);
+        state.channel.participants = participants.iter().map(|p| address(p)).collect();
+        state
+    }
+
+    #[test]
+    fn expects_the_signature_of_the_mover() {
+        // Participant `turnNum % n` moves, so participant 1 signs turn 5.
+        let from = fixtures::state(4);
+        let to = fixtures::state(5);
+        let signed = to.clone().sign(private_key(PRIVATE_KEY2)).unwrap();
+        assert!(
+            from.validate_peer_update(to.clone(), signed.signature.as_bytes())
+                == Ok(Status::NeedToCheckApp)
+        );
+
+        let signed = to.clone().sign(private_key(PRIVATE_KEY1)).unwrap();
+        assert_eq!(
+            from.validate_peer_update(to, signed.signature.as_bytes())
+                .err()
+                .map(|e| e.code()),
+            Some("SIGNER_MISMATCH")
+        );
+    }
+
+    #[test]
+    fn rejects_peer_updates_without_participants() {
+        let signature = signature_with_v(27);
+
+        let from = peer_update(1, &[]);
+        assert_eq!(
+            from.validate_peer_update(peer_update(2, &[]), signature).err(),
+            Some(Error::NoParticipants)
+        );
+    }
+
+    #[test]
+    fn parses_peer_update_options_from_one_object() {
+        let options: PeerUpdateOptions =
+            serde_json::from_value(json!({ "strictChecksums": true, "validateOutcome": true }))
+                .unwrap();
+        assert!(options.parse.strict_checksums);
+        assert!(options.validation.validate_outcome);
+
+        let options: PeerUpdateOptions = serde_json::from_value(json!({})).unwrap();
+        assert!(!options.parse.strict_checksums);
+        assert!(!options.validation.validate_outcome);
+    }
+
+    #[test]
+    fn parses_random_and_mutated_signatures_without_panicking() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let valid = signature_with_v(27);
+
+        for _ in 0..1_000 {
+            let len = (rng.next() % 80) as usize;
+            let random = Bytes(rng.bytes(len));
+
+            // A valid signature with a few bytes flipped.
+            let mut mutated = valid.clone();
+            for _ in 0..rng.next() % 4 {
+                let i = (rng.next() % 65) as usize;
+                mutated.0[i] ^= rng.next() as u8;
+            }
+
+            for signature in vec![random, mutated] {
+                let _ = RecoverableSignature::from_slice(&signature);
+                let _ = recover_message_signer(b"hello", &signature);
+                let _ = verify_signature(
+                    Bytes32::from([0; 32]),
+                    Address::zero(),
+                    signature.clone(),
+                );
+                let _ = serde_json::from_value::<RecoverableSignature>(json!(
+                    signature.to_vec().to_hex_string()
+                ));
+            }
+        }
+    }
 }
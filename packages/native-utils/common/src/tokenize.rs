@@ -1,10 +1,17 @@
-pub use ethabi::Token;
+pub use ethabi::{ParamType, Token};
 use ethereum_types::Address;
 
+use super::error::*;
+
 pub trait Tokenize {
     fn tokenize(&self) -> Token;
 }
 
+pub trait Detokenize: Sized {
+    fn param_type() -> ParamType;
+    fn detokenize(token: Token) -> Result<Self>;
+}
+
 impl Tokenize for bool {
     fn tokenize(&self) -> Token {
         Token::Bool(*self)
@@ -17,8 +24,52 @@ impl Tokenize for Address {
     }
 }
 
+impl Detokenize for Address {
+    fn param_type() -> ParamType {
+        ParamType::Address
+    }
+
+    fn detokenize(token: Token) -> Result<Self> {
+        match token {
+            Token::Address(address) => Ok(address),
+            token => Err(Error::InvalidEncoding(format!("expected address, got {:?}", token))),
+        }
+    }
+}
+
 impl<T: Tokenize> Tokenize for Vec<T> {
     fn tokenize(&self) -> Token {
         Token::Array(self.into_iter().map(Tokenize::tokenize).collect())
     }
 }
+
+impl<T: Detokenize> Detokenize for Vec<T> {
+    fn param_type() -> ParamType {
+        ParamType::Array(Box::new(T::param_type()))
+    }
+
+    fn detokenize(token: Token) -> Result<Self> {
+        match token {
+            Token::Array(tokens) => tokens.into_iter().map(T::detokenize).collect(),
+            token => Err(Error::InvalidEncoding(format!("expected array, got {:?}", token))),
+        }
+    }
+}
+
+impl<A: Detokenize, B: Detokenize> Detokenize for (A, B) {
+    fn param_type() -> ParamType {
+        ParamType::Tuple(vec![Box::new(A::param_type()), Box::new(B::param_type())])
+    }
+
+    fn detokenize(token: Token) -> Result<Self> {
+        match token {
+            Token::Tuple(tokens) if tokens.len() == 2 => {
+                let mut tokens = tokens.into_iter();
+                let a = A::detokenize(tokens.next().unwrap())?;
+                let b = B::detokenize(tokens.next().unwrap())?;
+                Ok((a, b))
+            }
+            token => Err(Error::InvalidEncoding(format!("expected 2-tuple, got {:?}", token))),
+        }
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ethabi::{encode, Token};
+use ethereum_types::{Address, U256};
+use secp256k1::SecretKey;
+use serde_derive::*;
+use serde_json::Value;
+
+use super::error::*;
+use super::state::*;
+use super::tokenize::*;
+use super::types::*;
+use super::utils::*;
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// The fields of `EIP712Domain`, in the order EIP-712 defines them.
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
+pub struct TypedDataField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+pub type TypedDataTypes = BTreeMap<String, Vec<TypedDataField>>;
+
+/// EIP-712 typed data, in the JSON format of `eth_signTypedData_v4`.
+///
+/// If `types` does not define `EIP712Domain`, it is derived from the fields
+/// present in `domain`, like ethers' `TypedDataEncoder` does.
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: TypedDataTypes,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+impl TypedData {
+    pub fn domain_separator(&self) -> Result<Bytes32> {
+        match self.types.get(DOMAIN_TYPE) {
+            Some(_) => hash_struct(&self.types, DOMAIN_TYPE, &self.domain),
+            None => {
+                let mut types = self.types.clone();
+                types.insert(DOMAIN_TYPE.into(), domain_fields(&self.domain)?);
+                hash_struct(&types, DOMAIN_TYPE, &self.domain)
+            }
+        }
+    }
+
+    pub fn hash_struct(&self) -> Result<Bytes32> {
+        hash_struct(&self.types, &self.primary_type, &self.message)
+    }
+
+    /// Computes `keccak256(0x19 || 0x01 || domainSeparator || hashStruct(message))`.
+    pub fn hash(&self) -> Result<Bytes32> {
+        let mut data = vec![0x19, 0x01];
+        data.extend_from_slice(&self.domain_separator()?);
+        data.extend_from_slice(&self.hash_struct()?);
+        Ok(keccak256(&data).into())
+    }
+
+    pub fn sign(&self, secret_key: &SecretKey) -> Result<RecoverableSignature> {
+        Ok(sign_digest(&self.digest()?, secret_key))
+    }
+
+    pub fn recover_signer(&self, signature: &Bytes) -> Result<String> {
+        recover_digest_signer(&self.digest()?, signature)
+    }
+
+    fn digest(&self) -> Result<[u8; 32]> {
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&self.hash()?);
+        Ok(digest)
+    }
+}
+
+fn invalid<T>(message: String) -> Result<T> {
+    Err(Error::InvalidTypedData(message))
+}
+
+fn domain_fields(domain: &Value) -> Result<Vec<TypedDataField>> {
+    let domain = match domain.as_object() {
+        Some(domain) => domain,
+        None => return invalid("domain must be an object".into()),
+    };
+
+    if let Some(key) = domain
+        .keys()
+        .find(|key| DOMAIN_FIELDS.iter().all(|(name, _)| name != key))
+    {
+        return invalid(format!("unknown domain field {}", key));
+    }
+
+    Ok(DOMAIN_FIELDS
+        .iter()
+        .filter(|(name, _)| domain.contains_key(*name))
+        .map(|(name, kind)| TypedDataField {
+            name: name.to_string(),
+            kind: kind.to_string(),
+        })
+        .collect())
+}
+
+/// Splits `Foo[2][]` into `Foo[2]` and the length of the outermost array, if
+/// the type is an array at all.
+fn split_array_type(kind: &str) -> Option<(&str, Option<usize>)> {
+    if !kind.ends_with(']') {
+        return None;
+    }
+    let open = kind.rfind('[')?;
+    let length = &kind[open + 1..kind.len() - 1];
+    let length = if length.is_empty() {
+        None
+    } else {
+        Some(length.parse().ok()?)
+    };
+    Some((&kind[..open], length))
+}
+
+fn base_type(kind: &str) -> &str {
+    match kind.find('[') {
+        Some(open) => &kind[..open],
+        None => kind,
+    }
+}
+
+fn fields<'a>(types: &'a TypedDataTypes, name: &str) -> Result<&'a Vec<TypedDataField>> {
+    match types.get(name) {
+        Some(fields) => Ok(fields),
+        None => invalid(format!("unknown type {}", name)),
+    }
+}
+
+fn collect_dependencies(types: &TypedDataTypes, name: &str, found: &mut BTreeSet<String>) {
+    if found.contains(name) || !types.contains_key(name) {
+        return;
+    }
+    found.insert(name.into());
+    for field in &types[name] {
+        collect_dependencies(types, base_type(&field.kind), found);
+    }
+}
+
+/// Encodes a struct type and the types it references, e.g.
+/// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+pub fn encode_type(types: &TypedDataTypes, primary_type: &str) -> Result<String> {
+    fields(types, primary_type)?;
+
+    let mut dependencies = BTreeSet::new();
+    collect_dependencies(types, primary_type, &mut dependencies);
+    dependencies.remove(primary_type);
+
+    let encode_struct = |name: &str| {
+        let fields: Vec<String> = types[name]
+            .iter()
+            .map(|field| format!("{} {}", field.kind, field.name))
+            .collect();
+        format!("{}({})", name, fields.join(","))
+    };
+
+    let mut encoded = encode_struct(primary_type);
+    for dependency in dependencies {
+        encoded.push_str(&encode_struct(&dependency));
+    }
+    Ok(encoded)
+}
+
+pub fn type_hash(types: &TypedDataTypes, primary_type: &str) -> Result<Bytes32> {
+    Ok(keccak256(encode_type(types, primary_type)?.as_bytes()).into())
+}
+
+/// Computes `hashStruct(data)` for a value of the given struct type.
+pub fn hash_struct(types: &TypedDataTypes, primary_type: &str, data: &Value) -> Result<Bytes32> {
+    let mut tokens = vec![type_hash(types, primary_type)?.tokenize()];
+    for field in fields(types, primary_type)? {
+        let value = match data.get(&field.name) {
+            Some(value) if !value.is_null() => value,
+            _ => return invalid(format!("missing value for {}.{}", primary_type, field.name)),
+        };
+        tokens.push(
+            encode_value(types, &field.kind, value).map_err(|error| match error {
+                Error::InvalidTypedData(message) => {
+                    Error::InvalidTypedData(format!("{}.{}: {}", primary_type, field.name, message))
+                }
+                error => error,
+            })?,
+        );
+    }
+    Ok(keccak256(&encode(&tokens)).into())
+}
+
+fn hash_token(bytes: &[u8]) -> Token {
+    Bytes32::from(keccak256(bytes)).tokenize()
+}
+
+fn encode_value(types: &TypedDataTypes, kind: &str, value: &Value) -> Result<Token> {
+    if let Some((element_kind, length)) = split_array_type(kind) {
+        let elements = match value.as_array() {
+            Some(elements) => elements,
+            None => return invalid(format!("expected an array for {}", kind)),
+        };
+        if let Some(length) = length {
+            if elements.len() != length {
+                return invalid(format!(
+                    "expected {} elements for {}, got {}",
+                    length,
+                    kind,
+                    elements.len()
+                ));
+            }
+        }
+        let tokens = elements
+            .iter()
+            .map(|element| encode_value(types, element_kind, element))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(hash_token(&encode(&tokens)));
+    }
+
+    if types.contains_key(kind) {
+        return Ok(hash_struct(types, kind, value)?.tokenize());
+    }
+
+    match kind {
+        "string" => match value.as_str() {
+            Some(s) => Ok(hash_token(s.as_bytes())),
+            None => invalid("expected a string".into()),
+        },
+        "bytes" => Ok(hash_token(&parse::<Bytes>(value, kind)?)),
+        "bool" => match value.as_bool() {
+            Some(b) => Ok(b.tokenize()),
+            None => invalid("expected a boolean".into()),
+        },
+        "address" => Ok(parse::<Address>(value, kind)?.tokenize()),
+        _ if kind.starts_with("bytes") => {
+            let size = parse_size(kind, "bytes", 1, 32)?;
+            let bytes = parse::<Bytes>(value, kind)?;
+            if bytes.len() != size {
+                return invalid(format!("expected {} bytes, got {}", size, bytes.len()));
+            }
+            Ok(Token::FixedBytes(bytes.0))
+        }
+        _ if kind.starts_with("uint") => {
+            let bits = parse_size(kind, "uint", 8, 256)?;
+            let n = parse::<Uint256>(&integer(value), kind)?.0;
+            if bits < 256 && n >> bits != U256::zero() {
+                return invalid(format!("value out of range for {}", kind));
+            }
+            Ok(Token::Uint(n))
+        }
+        _ if kind.starts_with("int") => {
+            let bits = parse_size(kind, "int", 8, 256)?;
+            Ok(Token::Int(parse_int(value, kind, bits)?))
+        }
+        _ => invalid(format!("unknown type {}", kind)),
+    }
+}
+
+fn parse<T: serde::de::DeserializeOwned>(value: &Value, kind: &str) -> Result<T> {
+    serde_json::from_value(value.clone())
+        .or_else(|error| invalid(format!("invalid {}: {}", kind, error)))
+}
+
+/// Parses the size of types like `bytes32`, `uint64` or `int8`.
+fn parse_size(kind: &str, prefix: &str, min: usize, max: usize) -> Result<usize> {
+    let size = match kind[prefix.len()..].parse::<usize>() {
+        Ok(size) => size,
+        Err(_) => return invalid(format!("unknown type {}", kind)),
+    };
+    let is_valid = size >= min && size <= max && (prefix == "bytes" || size % 8 == 0);
+    if !is_valid {
+        return invalid(format!("unknown type {}", kind));
+    }
+    Ok(size)
+}
+
+/// Integers may arrive as floats (e.g. `1e3`); turn floats without a
+/// fractional part back into JSON integers.
+fn integer(value: &Value) -> Value {
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+    match value.as_f64() {
+        Some(n) if value.is_f64() && n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+            if n < 0.0 {
+                Value::from(n as i64)
+            } else {
+                Value::from(n as u64)
+            }
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Parses a signed integer into its two's complement representation.
+fn parse_int(value: &Value, kind: &str, bits: usize) -> Result<U256> {
+    let (negative, magnitude) = match &integer(value) {
+        Value::String(s) if s.starts_with('-') => {
+            (true, parse::<Uint256>(&Value::from(&s[1..]), kind)?.0)
+        }
+        Value::Number(n) if n.is_i64() && n.as_i64().unwrap() < 0 => {
+            (true, U256::from(n.as_i64().unwrap().unsigned_abs()))
+        }
+        value => (false, parse::<Uint256>(value, kind)?.0),
+    };
+
+    let limit = U256::one() << (bits - 1);
+    if (negative && magnitude > limit) || (!negative && magnitude >= limit) {
+        return invalid(format!("value out of range for {}", kind));
+    }
+
+    if negative {
+        Ok((!magnitude).overflowing_add(U256::one()).0)
+    } else {
+        Ok(magnitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// The example from EIP-712.
+    fn mail() -> TypedData {
+        serde_json::from_value(json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" },
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" },
+                ],
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!",
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn hashes_eip712_example() {
+        let typed_data = mail();
+
+        assert_eq!(
+            encode_type(&typed_data.types, "Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            typed_data.domain_separator().unwrap().to_hex_string(),
+            "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            typed_data.hash_struct().unwrap().to_hex_string(),
+            "0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            typed_data.hash().unwrap().to_hex_string(),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn derives_domain_type() {
+        let mut typed_data = mail();
+        let expected = typed_data.domain_separator().unwrap();
+
+        typed_data.types.remove(DOMAIN_TYPE);
+        assert!(typed_data.domain_separator().unwrap() == expected);
+    }
+
+    #[test]
+    fn signs_eip712_example() {
+        let typed_data = mail();
+        let secret_key = SecretKey::parse(&keccak256(b"cow")).unwrap();
+
+        let signature = typed_data.sign(&secret_key).unwrap().as_bytes();
+        assert_eq!(
+            signature.to_hex_string(),
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+             07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562\
+             1c"
+        );
+        assert_eq!(
+            typed_data.recover_signer(&signature).unwrap(),
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let mut typed_data = mail();
+        typed_data.message["from"]["wallet"] = json!("not an address");
+        assert!(matches!(
+            typed_data.hash_struct(),
+            Err(Error::InvalidTypedData(message)) if message.starts_with("Mail.from: Person.wallet: ")
+        ));
+
+        let mut typed_data = mail();
+        typed_data
+            .message
+            .as_object_mut()
+            .unwrap()
+            .remove("contents");
+        assert_eq!(
+            typed_data.hash_struct().err(),
+            Some(Error::InvalidTypedData(
+                "missing value for Mail.contents".into()
+            ))
+        );
+
+        let mut typed_data = mail();
+        typed_data.primary_type = "Letter".into();
+        assert_eq!(
+            typed_data.hash_struct().err(),
+            Some(Error::InvalidTypedData("unknown type Letter".into()))
+        );
+    }
+
+    #[test]
+    fn encodes_atomic_values() {
+        let types = TypedDataTypes::new();
+
+        assert!(encode_value(&types, "int8", &json!(-128)).unwrap() == Token::Int(U256::MAX - 127));
+        assert!(encode_value(&types, "int8", &json!("-1")).unwrap() == Token::Int(U256::MAX));
+        assert!(encode_value(&types, "int8", &json!(128)).is_err());
+        assert!(encode_value(&types, "uint8", &json!("0xff")).unwrap() == Token::Uint(255.into()));
+        assert!(encode_value(&types, "uint8", &json!(256)).is_err());
+        assert!(encode_value(&types, "uint8", &json!(255.0)).unwrap() == Token::Uint(255.into()));
+        assert!(encode_value(&types, "int8", &json!(-1.0)).unwrap() == Token::Int(U256::MAX));
+        assert!(encode_value(&types, "uint8", &json!(1.5)).is_err());
+        assert!(encode_value(&types, "uint7", &json!(1)).is_err());
+        assert!(
+            encode_value(&types, "bytes2", &json!("0x0102")).unwrap()
+                == Token::FixedBytes(vec![1, 2])
+        );
+        assert!(encode_value(&types, "bytes2", &json!("0x01")).is_err());
+        assert!(encode_value(&types, "uint8[2]", &json!([1])).is_err());
+    }
+}
@@ -3,10 +3,11 @@ use std::fmt;
 use std::ops::Deref;
 use std::str::FromStr;
 
-use ethereum_types::U256;
+use ethereum_types::{Address, U256};
 use serde::de::{Error as SerdeError, *};
 use serde::ser::*;
 
+use super::error::{Error as NativeUtilsError, Result as NativeUtilsResult};
 use super::tokenize::*;
 
 pub trait ToHexString {
@@ -19,7 +20,7 @@ impl ToHexString for Vec<u8> {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Bytes(pub Vec<u8>);
 
 impl Deref for Bytes {
@@ -56,9 +57,35 @@ impl Tokenize for Bytes {
     }
 }
 
-#[derive(PartialEq)]
+impl Detokenize for Bytes {
+    fn param_type() -> ParamType {
+        ParamType::Bytes
+    }
+
+    fn detokenize(token: Token) -> NativeUtilsResult<Self> {
+        match token {
+            Token::Bytes(bytes) => Ok(Self(bytes)),
+            token => Err(NativeUtilsError::InvalidEncoding(format!(
+                "expected bytes, got {:?}",
+                token
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
 pub struct Bytes32(Vec<u8>);
 
+impl Bytes32 {
+    /// Left-pads an address with zeros, the way participants are turned into
+    /// allocation destinations.
+    pub fn from_address(address: &Address) -> Self {
+        let mut bytes = [0; 32];
+        bytes[12..].copy_from_slice(address.as_bytes());
+        Self::from(bytes)
+    }
+}
+
 impl From<[u8; 32]> for Bytes32 {
     fn from(bytes: [u8; 32]) -> Self {
         Self(bytes.into())
@@ -109,9 +136,34 @@ impl Tokenize for Bytes32 {
     }
 }
 
-#[derive(PartialEq, PartialOrd)]
+impl Detokenize for Bytes32 {
+    fn param_type() -> ParamType {
+        ParamType::FixedBytes(32)
+    }
+
+    fn detokenize(token: Token) -> NativeUtilsResult<Self> {
+        match token {
+            Token::FixedBytes(bytes) if bytes.len() == 32 => Ok(Self(bytes)),
+            token => Err(NativeUtilsError::InvalidEncoding(format!(
+                "expected bytes32, got {:?}",
+                token
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, PartialOrd)]
 pub struct Uint48(pub u64);
 
+impl Serialize for Uint48 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for Uint48 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -127,7 +179,7 @@ impl Tokenize for Uint48 {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Uint256(pub U256);
 
 impl From<U256> for Uint256 {
@@ -209,6 +261,21 @@ impl<'de> Visitor<'de> for Uint256Visitor {
     }
 }
 
+impl Serialize for Uint256 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Match the even-length hex strings that ethers' `BigNumber.toHexString()`
+        // produces, e.g. `0x00` or `0x0de0b6b3a7640000`.
+        let mut hex = format!("{:x}", self.0);
+        if hex.len() % 2 == 1 {
+            hex.insert(0, '0');
+        }
+        format!("0x{}", hex).serialize(serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for Uint256 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -223,3 +290,19 @@ impl Tokenize for Uint256 {
         Token::Uint(self.0)
     }
 }
+
+impl Detokenize for Uint256 {
+    fn param_type() -> ParamType {
+        ParamType::Uint(256)
+    }
+
+    fn detokenize(token: Token) -> NativeUtilsResult<Self> {
+        match token {
+            Token::Uint(n) => Ok(Self(n)),
+            token => Err(NativeUtilsError::InvalidEncoding(format!(
+                "expected uint256, got {:?}",
+                token
+            ))),
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use secp256k1::PublicKey;
+use secp256k1::{PublicKey, PublicKeyFormat};
+
+use super::error::*;
+use super::state::parse_private_key;
+use super::types::*;
 
 pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
     use tiny_keccak::{Hasher, Keccak};
@@ -9,10 +13,10 @@ pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
     output
 }
 
-pub fn hash_message(msg: &Vec<u8>) -> [u8; 32] {
+pub fn hash_message(msg: &[u8]) -> [u8; 32] {
     const PREFIX: &str = "\x19Ethereum Signed Message:\n";
     let mut eth_msg = format!("{}{}", PREFIX, msg.len()).into_bytes();
-    eth_msg.extend_from_slice(msg.as_slice());
+    eth_msg.extend_from_slice(msg);
     keccak256(&eth_msg)
 }
 
@@ -23,6 +27,25 @@ pub fn public_key_to_address(public_key: PublicKey) -> Vec<u8> {
     hash[12..].into()
 }
 
+/// Returns the checksummed address belonging to the given private key.
+pub fn address_from_private_key(private_key: &Bytes) -> Result<String> {
+    let secret_key = parse_private_key(private_key)?;
+    let public_key = PublicKey::from_secret_key(&secret_key);
+    Ok(checksum_address(public_key_to_address(public_key)))
+}
+
+/// Returns the checksummed address belonging to the given public key, which
+/// may be in compressed (33 bytes) or uncompressed (65 bytes) form.
+pub fn address_from_public_key(public_key: &Bytes) -> Result<String> {
+    let format = match public_key.len() {
+        33 => PublicKeyFormat::Compressed,
+        65 => PublicKeyFormat::Full,
+        _ => return Err(Error::InvalidPublicKey),
+    };
+    let public_key = PublicKey::parse_slice(public_key, Some(format)).or(Err(Error::InvalidPublicKey))?;
+    Ok(checksum_address(public_key_to_address(public_key)))
+}
+
 pub fn checksum_address(address: Vec<u8>) -> String {
     let s = hex::encode(&address);
     let unchecksummed = s.as_bytes();
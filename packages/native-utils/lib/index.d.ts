@@ -1,4 +1,12 @@
-import { Bytes32, Channel, State } from '@statechannels/nitro-protocol'
+import {
+  AllocationAssetOutcome,
+  AllocationItem,
+  Bytes32,
+  Channel,
+  Guarantee,
+  Outcome,
+  State,
+} from '@statechannels/nitro-protocol'
 
 /**
  * A Nitro state with its state hash and signature from signing the state.
@@ -20,12 +28,177 @@ export interface StateWithHashAndSignature {
   signature: string
 }
 
+/**
+ * What the AssetHolder's `transfer` or `transferAll` pays out, as computed by
+ * `computeTransferPayouts`.
+ */
+export interface TransferPayouts {
+  /**
+   * The allocation with the paid out amounts deducted.
+   */
+  newAllocation: AllocationItem[]
+
+  /**
+   * The amount paid out for each of the given indices, or for every
+   * allocation item if no indices were given.
+   */
+  payouts: string[]
+
+  totalPayouts: string
+
+  /**
+   * Whether every item of the new allocation is zero, in which case the
+   * AssetHolder deletes the outcome.
+   */
+  safeToDelete: boolean
+}
+
+/**
+ * What the AssetHolder's `claimAll` pays out of a guarantor channel's holdings,
+ * as computed by `computeClaimPayouts`.
+ */
+export interface ClaimPayouts {
+  /**
+   * The target allocation with the paid out amounts deducted and the items
+   * that were paid out in full removed.
+   */
+  newAllocation: AllocationItem[]
+
+  /**
+   * The amount paid out for each item of the target allocation.
+   */
+  payouts: string[]
+
+  totalPayouts: string
+
+  /**
+   * What is left of the guarantor's holdings.
+   */
+  remainingHoldings: string
+}
+
+/**
+ * Whether, and how much, a participant may deposit for a destination, as
+ * computed by `computeDepositSafety`.
+ */
+export interface DepositSafety {
+  /**
+   * The holdings at which it is safe to deposit, i.e. the total allocated to
+   * the items ahead of the destination. Pass this as `expectedHeld` to the
+   * AssetHolder's `deposit`.
+   */
+  expectedHeld: string
+
+  /**
+   * The amount left to deposit for the destination once it is safe to do so,
+   * taking into account what others have already deposited.
+   */
+  amountRequired: string
+
+  /**
+   * Whether everyone ahead of the destination has deposited.
+   */
+  safeToDeposit: boolean
+}
+
+/**
+ * A signature in the `{r, s, v}` form that the nitro-protocol contracts take.
+ */
+export interface SplitSignature {
+  r: string
+  s: string
+  v: number
+}
+
+/**
+ * EIP-712 typed data in the format of `eth_signTypedData_v4`. If `types` does
+ * not include `EIP712Domain`, it is derived from the fields of `domain`.
+ */
+export interface TypedData {
+  types: Record<string, { name: string; type: string }[]>
+  primaryType: string
+  domain: Record<string, unknown>
+  message: Record<string, unknown>
+}
+
+export interface DerivedKey {
+  /**
+   * A private key, usable with `signState` and `Signer`.
+   */
+  privateKey: string
+
+  /**
+   * The checksummed address belonging to the private key.
+   */
+  address: string
+}
+
+/**
+ * The parts of a Nitro state, besides the channel, that must not change over
+ * the lifetime of a channel.
+ */
+export interface FixedPart {
+  appDefinition: string
+  challengeDuration: number
+}
+
+/**
+ * The parts of a Nitro state that may change from one turn to the next.
+ */
+export interface StateVariables {
+  turnNum: number
+  isFinal: boolean
+  outcome: Outcome
+  appData: string
+}
+
+/**
+ * Options for parsing the inputs of functions such as `signState` and
+ * `validatePeerUpdate`.
+ */
+export interface ParseOptions {
+  /**
+   * Reject mixed-case addresses whose EIP-55 checksum is wrong, throwing an
+   * `INVALID_ADDRESS_CHECKSUM` error that names the offending field, e.g.
+   * `state.channel.participants[1]`. Defaults to `false`.
+   */
+  strictChecksums?: boolean
+}
+
+/**
+ * Opt-in checks for `validatePeerUpdate`.
+ */
+export interface ValidationOptions {
+  /**
+   * Reject peer updates whose outcome has duplicate asset holders, duplicate
+   * destinations within an allocation, allocations whose total overflows
+   * uint256, or guarantees that target the channel itself, throwing an
+   * `INVALID_OUTCOME` error. Defaults to `false`.
+   */
+  validateOutcome?: boolean
+}
+
+/**
+ * The error thrown by fallible functions such as `signState`, `recoverAddress`
+ * and `validatePeerUpdate`, and by any function given malformed input. Input
+ * errors have the code `INVALID_INPUT` and a message naming the offending
+ * field, e.g. `state.outcome[0].allocationItems[2].amount: negative integer`.
+ */
+export interface NativeUtilsError extends Error {
+  /**
+   * A stable, machine-readable error code, e.g. `INVALID_SIGNATURE_LENGTH`
+   * or `TURN_NUM_NOT_INCREMENTED`.
+   */
+  code: string
+}
+
 /**
  * Computes the ID for the given channel.
  *
  * @param channel Channel data.
+ * @param options Parsing options, e.g. to enforce address checksums.
  */
-export function getChannelId(channel: Channel): string
+export function getChannelId(channel: Channel, options?: ParseOptions): string
 
 /**
  * Encodes the outcome part of a Nitro state.
@@ -34,6 +207,61 @@ export function getChannelId(channel: Channel): string
  */
 export function encodeOutcome(state: State): string
 
+/**
+ * Decodes an ABI-encoded outcome, e.g. as produced by `encodeOutcome` or
+ * emitted in a `ChallengeRegistered` event.
+ *
+ * @param encoded The ABI-encoded outcome as a hex string.
+ */
+export function decodeOutcome(encoded: string): Outcome
+
+/**
+ * Computes what the AssetHolder's `transfer` pays out for the allocation items
+ * at `indices`, mirroring nitro's `_computeNewAllocation`. Items are funded in
+ * order of priority, so when the channel is only partially funded, items
+ * after the first underfunded one receive nothing.
+ *
+ * @param assetOutcome An allocation asset outcome.
+ * @param holdings The AssetHolder's holdings for the channel.
+ * @param indices Strictly increasing indices into the allocation. If omitted
+ *   or empty, every item is paid out, as with `transferAll`.
+ */
+export function computeTransferPayouts(
+  assetOutcome: AllocationAssetOutcome,
+  holdings: string,
+  indices?: number[],
+): TransferPayouts
+
+/**
+ * Computes what the AssetHolder's `claimAll` pays out of a guarantor channel's
+ * holdings. The items of the target allocation are paid for each of the
+ * guarantee's destinations in turn, and then in the allocation's own order.
+ *
+ * @param guarantee The guarantor channel's guarantee.
+ * @param target The allocation of the guarantee's target channel.
+ * @param holdings The AssetHolder's holdings for the guarantor channel.
+ */
+export function computeClaimPayouts(
+  guarantee: Guarantee,
+  target: AllocationAssetOutcome,
+  holdings: string,
+): ClaimPayouts
+
+/**
+ * Computes when it is safe for a participant to deposit into the AssetHolder,
+ * following the direct-funding protocol: a participant deposits only once the
+ * holdings cover everything allocated ahead of them.
+ *
+ * @param assetOutcome An allocation asset outcome.
+ * @param destination The destination to deposit for.
+ * @param holdings The AssetHolder's current holdings for the channel.
+ */
+export function computeDepositSafety(
+  assetOutcome: AllocationAssetOutcome,
+  destination: string,
+  holdings: string,
+): DepositSafety
+
 /**
  * Hashes the app part of a Nitro state.
  *
@@ -61,32 +289,228 @@ export function hashOutcome(state: State): string
  * Hashes a Nitro state.
  *
  * @param state A Nitro state.
+ * @param options Parsing options, e.g. to enforce address checksums.
  */
-export function hashState(state: State): string
+export function hashState(state: State, options?: ParseOptions): string
 
 /**
  * Signs the state with the given private key.
  *
  * @param state A Nitro state.
  * @param privateKey A private Ethereum key.
+ * @param options Parsing options, e.g. to enforce address checksums.
  */
-export function signState(state: State, privateKey: string): StateWithHashAndSignature
+export function signState(state: State, privateKey: string, options?: ParseOptions): StateWithHashAndSignature
 
 /**
  * Recovers the signer address from a signed Nitro state.
  *
  * @param state A Nitro state.
+ * @param signature A signature resulting from a previous call to `signState`,
+ *   in either the 65-byte or the 64-byte compact form.
+ * @param options Parsing options, e.g. to enforce address checksums.
+ */
+export function recoverAddress(state: State, signature: string, options?: ParseOptions): string
+
+/**
+ * Verifies that a state hash was signed by the given address.
+ *
+ * @param hash A 32-byte state hash, computed with `hashState`.
+ * @param address The address of the expected signer.
  * @param signature A signature resulting from a previous call to `signState`.
  */
-export function recoverAddress(state: State, signature: string): string
+export function verifySignature(hash: Bytes32, address: string, signature: string): boolean
 
 /**
- * Verifies a signature.
+ * Converts a signature into the 64-byte compact form `r || yParityAndS`
+ * specified in EIP-2098.
+ *
+ * @param signature A 65-byte signature, e.g. from `signState`.
+ */
+export function toCompactSignature(signature: string): string
+
+/**
+ * Converts a 64-byte EIP-2098 compact signature into the usual 65-byte form
+ * `r || s || v`. 65-byte signatures are returned as they are, with `v`
+ * normalized to 27 or 28.
+ *
+ * @param signature A compact signature, e.g. from `toCompactSignature`.
+ */
+export function fromCompactSignature(signature: string): string
+
+/**
+ * Splits a signature into the `{r, s, v}` form that the nitro-protocol
+ * contracts take, e.g. to build `forceMove` calldata.
+ *
+ * @param signature A 65-byte or 64-byte compact signature, or an already
+ *   split signature.
+ */
+export function splitSignature(signature: string | SplitSignature): SplitSignature
+
+/**
+ * Joins a signature split with `splitSignature` into the 65-byte form
+ * `r || s || v`.
+ *
+ * @param signature A split signature, or a 65-byte or 64-byte compact signature.
+ */
+export function joinSignature(signature: string | SplitSignature): string
+
+/**
+ * Signs a message as specified in EIP-191, i.e. like `personal_sign` or
+ * ethers' `Signer.signMessage`.
+ *
+ * @param message The hex-encoded message bytes.
+ * @param privateKey A private Ethereum key.
+ */
+export function signMessage(message: string, privateKey: string): string
+
+/**
+ * Recovers the checksummed address that signed a message with `signMessage`.
+ *
+ * @param message The hex-encoded message bytes.
+ * @param signature A signature resulting from a previous call to `signMessage`.
+ */
+export function recoverMessageSigner(message: string, signature: string): string
+
+/**
+ * Computes the EIP-712 hash of typed data, i.e.
+ * `keccak256(0x19 || 0x01 || domainSeparator || hashStruct(message))`.
+ *
+ * @param typedData Typed data in the format of `eth_signTypedData_v4`.
+ */
+export function hashTypedData(typedData: TypedData): string
+
+/**
+ * Signs typed data as specified in EIP-712, i.e. like `eth_signTypedData_v4`.
+ *
+ * @param typedData Typed data in the format of `eth_signTypedData_v4`.
+ * @param privateKey A private Ethereum key.
+ */
+export function signTypedData(typedData: TypedData, privateKey: string): string
+
+/**
+ * Recovers the checksummed address that signed typed data with `signTypedData`.
+ *
+ * @param typedData Typed data in the format of `eth_signTypedData_v4`.
+ * @param signature A signature resulting from a previous call to `signTypedData`.
+ */
+export function recoverTypedDataSigner(typedData: TypedData, signature: string): string
+
+/**
+ * Computes the checksummed address belonging to a private key.
+ *
+ * @param privateKey A private Ethereum key.
+ */
+export function getAddressFromPrivateKey(privateKey: string): string
+
+/**
+ * Computes the checksummed address belonging to a public key.
+ *
+ * @param publicKey A compressed (33 bytes) or uncompressed (65 bytes) public key.
+ */
+export function getAddressFromPublicKey(publicKey: string): string
+
+/**
+ * Derives the `index`-th Ethereum key, i.e. the key at `m/44'/60'/0'/0/index`,
+ * from a seed.
+ *
+ * @param seed A seed of 16 to 64 bytes.
+ * @param index The address index.
+ */
+export function deriveKeyFromSeed(seed: string, index: number): DerivedKey
+
+/**
+ * Derives the `index`-th Ethereum key, i.e. the key at `m/44'/60'/0'/0/index`,
+ * from an English BIP-39 mnemonic.
+ *
+ * @param mnemonic A BIP-39 mnemonic.
+ * @param index The address index.
+ * @param passphrase An optional BIP-39 passphrase.
+ */
+export function deriveKeyFromMnemonic(mnemonic: string, index: number, passphrase?: string): DerivedKey
+
+/**
+ * Like `signState`, but the native bindings sign the state on libuv's thread
+ * pool instead of blocking the event loop.
+ *
+ * @param state A Nitro state.
+ * @param privateKey A private Ethereum key.
+ * @param options Parsing options, e.g. to enforce address checksums.
+ */
+export function signStateAsync(
+  state: State,
+  privateKey: string,
+  options?: ParseOptions,
+): Promise<StateWithHashAndSignature>
+
+/**
+ * Like `recoverAddress`, but the native bindings recover the address on
+ * libuv's thread pool instead of blocking the event loop.
  *
  * @param state A Nitro state.
  * @param signature A signature resulting from a previous call to `signState`.
+ * @param options Parsing options, e.g. to enforce address checksums.
+ */
+export function recoverAddressAsync(state: State, signature: string, options?: ParseOptions): Promise<string>
+
+/**
+ * Like `validatePeerUpdate`, but the native bindings validate the update on
+ * libuv's thread pool instead of blocking the event loop.
+ *
+ * @param state Current state.
+ * @param peerUpdate Next state suggested by peer.
+ * @param signature Peer's signature for next state.
+ * @param options Parsing and validation options, e.g. to enforce address
+ *   checksums or to validate the outcome.
+ */
+export function validatePeerUpdateAsync(
+  state: State,
+  peerUpdate: State,
+  signature: string,
+  options?: ParseOptions & ValidationOptions,
+): Promise<string>
+
+/**
+ * Hashes many Nitro states in one call. The native bindings hash the states
+ * in parallel.
+ *
+ * A state that fails to parse results in an error at the corresponding index
+ * rather than failing the whole batch.
+ *
+ * @param states Nitro states.
  */
- export function verifySignature(hash: Bytes32, address: string, signature: string): boolean
+export function hashStates(states: State[]): (string | NativeUtilsError)[]
+
+/**
+ * Signs many Nitro states with the same private key in one call. The native
+ * bindings sign the states in parallel.
+ *
+ * A state that fails to parse results in an error at the corresponding index
+ * rather than failing the whole batch. An invalid private key fails the whole
+ * batch.
+ *
+ * @param states Nitro states.
+ * @param privateKey A private Ethereum key.
+ */
+export function signStates(
+  states: State[],
+  privateKey: string,
+): (StateWithHashAndSignature | NativeUtilsError)[]
+
+/**
+ * Recovers the signer addresses of many signed Nitro states in one call. The
+ * native bindings recover the addresses in parallel.
+ *
+ * A state that fails to parse or a signature that fails to recover results in
+ * an error at the corresponding index rather than failing the whole batch.
+ *
+ * @param states Nitro states.
+ * @param signatures One signature per state.
+ */
+export function recoverAddresses(
+  states: State[],
+  signatures: string[],
+): (string | NativeUtilsError)[]
 
  /**
  * Validate peer update.
@@ -94,5 +518,80 @@ export function recoverAddress(state: State, signature: string): string
  * @param state A Nitro state.
  * @param peer_update Next state suggested by peer
  * @param signature Peer's signature for next state.
+ * @param options Parsing and validation options, e.g. to enforce address
+ *   checksums or to validate the outcome.
+ */
+  export function validatePeerUpdate(state, peer_update, signature, options?: ParseOptions & ValidationOptions): string
+
+/**
+ * Validates that a sequence of states is supported by the signatures of all
+ * participants, mirroring ForceMove's `_requireStateSupportedBy`.
+ *
+ * @param channel Channel data.
+ * @param fixedPart The app definition and challenge duration of the channel.
+ * @param variableParts A sequence of states with consecutive turn numbers.
+ * @param signatures One signature per participant.
+ * @param whoSignedWhat For each participant, the index into `variableParts`
+ *   of the state they signed.
+ * @param options Parsing options, e.g. to enforce address checksums.
+ */
+export function validateSupportProof(
+  channel: Channel,
+  fixedPart: FixedPart,
+  variableParts: StateVariables[],
+  signatures: string[],
+  whoSignedWhat: number[],
+  options?: ParseOptions,
+): string
+
+/**
+ * Registers a built-in native app validator for the given app definition, so
+ * that `validatePeerUpdate` checks the app's transition rules locally rather
+ * than returning `NeedToCheckApp`.
+ *
+ * @param appDefinition The address of the app contract.
+ * @param name The built-in app.
+ */
+export function registerApp(
+  appDefinition: string,
+  name: 'SingleAssetPayments' | 'CountingApp' | 'TrivialApp',
+): void
+
+/**
+ * Removes the native app validator registered for the given app definition.
+ *
+ * @param appDefinition The address of the app contract.
+ * @returns Whether a validator was registered.
+ */
+export function unregisterApp(appDefinition: string): boolean
+
+/**
+ * A private key that is parsed once and kept in native memory, rather than
+ * being passed around as a string for every call to `signState`.
  */
-  export function validatePeerUpdate(state, peer_update, signature): string
\ No newline at end of file
+export class Signer {
+  /**
+   * @param privateKey A private Ethereum key.
+   * @throws {NativeUtilsError} If the private key is invalid.
+   */
+  constructor(privateKey: string)
+
+  /**
+   * The checksummed address belonging to the private key.
+   */
+  readonly address: string
+
+  /**
+   * Signs a Nitro state with the signer's private key.
+   *
+   * @param state A Nitro state.
+   */
+  signState(state: State): StateWithHashAndSignature
+
+  /**
+   * Signs a message with the signer's private key, like `signMessage`.
+   *
+   * @param message The hex-encoded message bytes.
+   */
+  signMessage(message: string): string
+}
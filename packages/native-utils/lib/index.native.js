@@ -2,6 +2,11 @@ const {
   getChannelId,
 
   encodeOutcome,
+  decodeOutcome,
+
+  computeTransferPayouts,
+  computeClaimPayouts,
+  computeDepositSafety,
 
   hashAppPart,
   hashMessage,
@@ -11,29 +16,113 @@ const {
   signState,
   recoverAddress,
   verifySignature,
+
+  toCompactSignature,
+  fromCompactSignature,
+
+  splitSignature,
+  joinSignature,
+
+  signMessage,
+  recoverMessageSigner,
+
+  hashTypedData,
+  signTypedData,
+  recoverTypedDataSigner,
+
+  getAddressFromPrivateKey,
+  getAddressFromPublicKey,
+
+  deriveKeyFromSeed,
+  deriveKeyFromMnemonic,
+
+  signStateAsync,
+  recoverAddressAsync,
+  validatePeerUpdateAsync,
+
+  hashStates,
+  signStates,
+  recoverAddresses,
+
   validatePeerUpdate,
+  validateSupportProof,
+
+  registerApp,
+  unregisterApp,
+
+  Signer: NativeSigner,
 } = require('../native/index.node')
 
+function toError({ code, message }) {
+  const error = new Error(message)
+  error.code = code
+  return error
+}
+
+function promisify(fn) {
+  return (...args) =>
+    new Promise((resolve, reject) => fn(...args, (error, result) => (error ? reject(error) : resolve(result))))
+}
+
+const signStateTask = promisify(signStateAsync)
+const recoverAddressTask = promisify(recoverAddressAsync)
+const validatePeerUpdateTask = promisify(validatePeerUpdateAsync)
+
 function unwrapResult({ Ok, Err }) {
   if (Err) {
-    throw Err
+    throw toError(Err)
   } else {
     return Ok
   }
 }
 
+// Batch functions report a result per item instead of throwing.
+function resultOrError({ Ok, Err }) {
+  return Err ? toError(Err) : Ok
+}
+
+class Signer {
+  constructor(privateKey) {
+    this.signer = new NativeSigner(privateKey)
+  }
+
+  get address() {
+    return this.signer.address()
+  }
+
+  signState(state) {
+    const { hash, signature } = this.signer.signState(state)
+    return {
+      state,
+      hash,
+      signature,
+    }
+  }
+
+  signMessage(message) {
+    return this.signer.signMessage(message)
+  }
+}
+
 module.exports = {
-  getChannelId,
+  getChannelId: (channel, options) => unwrapResult(getChannelId(channel, options)),
 
   encodeOutcome,
+  decodeOutcome: encoded => unwrapResult(decodeOutcome(encoded)),
+
+  computeTransferPayouts: (assetOutcome, holdings, indices) =>
+    unwrapResult(computeTransferPayouts(assetOutcome, holdings, indices)),
+  computeClaimPayouts,
+  computeDepositSafety: (assetOutcome, destination, holdings) =>
+    unwrapResult(computeDepositSafety(assetOutcome, destination, holdings)),
 
   hashAppPart,
   hashMessage,
   hashOutcome,
-  hashState,
+  hashState: (state, options) => unwrapResult(hashState(state, options)),
 
-  signState: (state, privateKey) => {
-    const { hash, signature } = unwrapResult(signState(state, privateKey))
+  signState: (state, privateKey, options) => {
+    const { hash, signature } = unwrapResult(signState(state, privateKey, options))
     return {
       state,
       hash,
@@ -41,9 +130,61 @@ module.exports = {
     }
   },
 
-  recoverAddress: (state, signature) => unwrapResult(recoverAddress(state, signature)),
+  recoverAddress: (state, signature, options) => unwrapResult(recoverAddress(state, signature, options)),
 
   verifySignature: (hash, address, signature) => unwrapResult(verifySignature(hash, address, signature)),
 
-  validatePeerUpdate: (state, peer_update, signature) => unwrapResult(validatePeerUpdate(state, peer_update, signature)),
+  toCompactSignature: signature => unwrapResult(toCompactSignature(signature)),
+  fromCompactSignature: signature => unwrapResult(fromCompactSignature(signature)),
+
+  splitSignature,
+  joinSignature,
+
+  signMessage: (message, privateKey) => unwrapResult(signMessage(message, privateKey)),
+  recoverMessageSigner: (message, signature) => unwrapResult(recoverMessageSigner(message, signature)),
+
+  hashTypedData: typedData => unwrapResult(hashTypedData(typedData)),
+  signTypedData: (typedData, privateKey) => unwrapResult(signTypedData(typedData, privateKey)),
+  recoverTypedDataSigner: (typedData, signature) => unwrapResult(recoverTypedDataSigner(typedData, signature)),
+
+  getAddressFromPrivateKey: privateKey => unwrapResult(getAddressFromPrivateKey(privateKey)),
+  getAddressFromPublicKey: publicKey => unwrapResult(getAddressFromPublicKey(publicKey)),
+
+  deriveKeyFromSeed: (seed, index) => unwrapResult(deriveKeyFromSeed(seed, index)),
+  deriveKeyFromMnemonic: (mnemonic, index, passphrase) =>
+    unwrapResult(deriveKeyFromMnemonic(mnemonic, index, passphrase)),
+
+  signStateAsync: async (state, privateKey, options) => {
+    const { hash, signature } = await signStateTask(state, privateKey, options)
+    return {
+      state,
+      hash,
+      signature,
+    }
+  },
+
+  recoverAddressAsync: (state, signature, options) => recoverAddressTask(state, signature, options),
+
+  validatePeerUpdateAsync: (state, peerUpdate, signature, options) =>
+    validatePeerUpdateTask(state, peerUpdate, signature, options),
+
+  hashStates: states => hashStates(states).map(resultOrError),
+
+  signStates: (states, privateKey) =>
+    unwrapResult(signStates(states, privateKey))
+      .map(resultOrError)
+      .map((result, i) => (result instanceof Error ? result : { state: states[i], ...result })),
+
+  recoverAddresses: (states, signatures) => unwrapResult(recoverAddresses(states, signatures)).map(resultOrError),
+
+  validatePeerUpdate: (state, peer_update, signature, options) =>
+    unwrapResult(validatePeerUpdate(state, peer_update, signature, options)),
+
+  validateSupportProof: (channel, fixedPart, variableParts, signatures, whoSignedWhat, options) =>
+    unwrapResult(validateSupportProof(channel, fixedPart, variableParts, signatures, whoSignedWhat, options)),
+
+  registerApp: (appDefinition, name) => unwrapResult(registerApp(appDefinition, name)),
+  unregisterApp,
+
+  Signer,
 }
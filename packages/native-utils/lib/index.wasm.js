@@ -2,6 +2,11 @@ const {
   getChannelId,
 
   encodeOutcome,
+  decodeOutcome,
+
+  computeTransferPayouts,
+  computeClaimPayouts,
+  computeDepositSafety,
 
   hashAppPart,
   hashMessage,
@@ -11,21 +16,79 @@ const {
   signState,
   recoverAddress,
   verifySignature,
-  validatePeerUpdate,  
+
+  toCompactSignature,
+  fromCompactSignature,
+
+  splitSignature,
+  joinSignature,
+
+  signMessage,
+  recoverMessageSigner,
+
+  hashTypedData,
+  signTypedData,
+  recoverTypedDataSigner,
+
+  getAddressFromPrivateKey,
+  getAddressFromPublicKey,
+
+  deriveKeyFromSeed,
+  deriveKeyFromMnemonic,
+
+  hashStates,
+  signStates,
+  recoverAddresses,
+
+  validatePeerUpdate,
+  validateSupportProof,
+
+  registerApp,
+  unregisterApp,
+
+  Signer: WasmSigner,
 } = require('@statechannels/wasm-utils')
 
+class Signer {
+  constructor(privateKey) {
+    this.signer = new WasmSigner(privateKey)
+  }
+
+  get address() {
+    return this.signer.address
+  }
+
+  signState(state) {
+    const { hash, signature } = this.signer.signState(state)
+    return {
+      state,
+      hash,
+      signature,
+    }
+  }
+
+  signMessage(message) {
+    return this.signer.signMessage(message)
+  }
+}
+
 module.exports = {
   getChannelId,
 
   encodeOutcome,
+  decodeOutcome,
+
+  computeTransferPayouts,
+  computeClaimPayouts,
+  computeDepositSafety,
 
   hashAppPart,
   hashMessage,
   hashOutcome,
   hashState,
 
-  signState: (state, privateKey) => {
-    const { hash, signature } = signState(state, privateKey)
+  signState: (state, privateKey, options) => {
+    const { hash, signature } = signState(state, privateKey, options)
     return {
       state,
       hash,
@@ -35,5 +98,47 @@ module.exports = {
 
   recoverAddress,
   verifySignature,
+
+  toCompactSignature,
+  fromCompactSignature,
+
+  splitSignature,
+  joinSignature,
+
+  signMessage,
+  recoverMessageSigner,
+
+  hashTypedData,
+  signTypedData,
+  recoverTypedDataSigner,
+
+  getAddressFromPrivateKey,
+  getAddressFromPublicKey,
+
+  deriveKeyFromSeed,
+  deriveKeyFromMnemonic,
+
+  // There is no thread pool to offload work to in WASM, so the async variants
+  // do the work synchronously and return an already settled promise.
+  signStateAsync: async (state, privateKey, options) => module.exports.signState(state, privateKey, options),
+  recoverAddressAsync: async (state, signature, options) => recoverAddress(state, signature, options),
+  validatePeerUpdateAsync: async (state, peerUpdate, signature, options) =>
+    validatePeerUpdate(state, peerUpdate, signature, options),
+
+  hashStates,
+
+  signStates: (states, privateKey) =>
+    signStates(states, privateKey).map((result, i) =>
+      result instanceof Error ? result : { state: states[i], ...result },
+    ),
+
+  recoverAddresses,
+
   validatePeerUpdate,
+  validateSupportProof,
+
+  registerApp,
+  unregisterApp,
+
+  Signer,
 }
@@ -1,19 +1,85 @@
 use std::ops::Deref;
 
 use neon::prelude::*;
-use neon_serde::*;
+use neon_serde::{from_value, to_value};
+use rayon::prelude::*;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 
 use statechannels_native_utils_common::prelude::*;
 
-export! {
-  fn getChannelId(channel: Channel) -> String {
-    channel.id().to_hex_string()
+/// Like `neon_serde::export!`, except that it leaves registering the module to
+/// `register_module!` below, so that functions which need a `FunctionContext`
+/// (e.g. to schedule tasks) can be exported alongside, and that it reports
+/// invalid arguments with the path of the offending field.
+macro_rules! serde_export {
+  ($(
+    fn $name:ident($( $arg:ident : $atype:ty ),*) -> $ret:ty $code:block
+  )*) => (
+    $(
+      #[allow(non_snake_case)]
+      fn $name($( $arg: $atype ),*) -> $ret $code
+    )*
+
+    fn export_serde_functions(m: &mut ModuleContext) -> NeonResult<()> {
+      $(
+        m.export_function(stringify!($name), |mut cx| {
+          let mut _arg_index = 0;
+
+          $(
+            let $arg = match cx.argument_opt(_arg_index) {
+              Some(value) => value,
+              None => cx.undefined().upcast(),
+            };
+            let $arg: $atype = parse_argument(&mut cx, $arg, &camel_case(stringify!($arg)))?;
+            _arg_index += 1;
+          )*
+
+          let result = $name($( $arg ),*);
+          let handle = to_value(&mut cx, &result)?;
+          Ok(handle)
+        })?;
+      )*
+      Ok(())
+    }
+  )
+}
+
+serde_export! {
+  fn getChannelId(channel: Value, options: Option<ParseOptions>) -> Result<String, Error> {
+    let options = options.unwrap_or_default();
+    let channel: Channel = parse_value(channel, "channel", &options)?;
+    Ok(channel.id().to_hex_string())
   }
 
   fn encodeOutcome(state: State) -> String {
     state.outcome.encode().to_hex_string()
   }
 
+  fn decodeOutcome(outcome: Bytes) -> Result<Outcome, Error> {
+    Outcome::decode(&outcome)
+  }
+
+  fn computeTransferPayouts(
+    asset_outcome: AllocationAssetOutcome,
+    holdings: Uint256,
+    indices: Option<Vec<usize>>
+  ) -> Result<TransferPayouts, Error> {
+    asset_outcome.compute_transfer_payouts(&holdings, &indices.unwrap_or_default())
+  }
+
+  fn computeClaimPayouts(guarantee: Guarantee, target: AllocationAssetOutcome, holdings: Uint256) -> ClaimPayouts {
+    guarantee.compute_claim_payouts(&target, &holdings)
+  }
+
+  fn computeDepositSafety(
+    asset_outcome: AllocationAssetOutcome,
+    destination: Bytes32,
+    holdings: Uint256
+  ) -> Result<DepositSafety, Error> {
+    asset_outcome.deposit_safety(&destination, &holdings)
+  }
+
   fn hashAppPart(state: State) -> String {
     state.hash_app_part().to_hex_string()
   }
@@ -22,23 +88,379 @@ export! {
     state.outcome.hash().to_hex_string()
   }
 
-  fn hashState(state: State) -> String {
-    state.hash().to_hex_string()
+  fn hashState(state: Value, options: Option<ParseOptions>) -> Result<String, Error> {
+    let options = options.unwrap_or_default();
+    let state: State = parse_value(state, "state", &options)?;
+    Ok(state.hash().to_hex_string())
   }
 
   fn hashMessage(msg: Bytes) -> String {
     Bytes32::from(hash_message(&msg.deref())).to_hex_string()
   }
 
-  fn signState(state: State, private_key: Bytes) -> Result<StateSignature, &'static str> {
+  fn signState(state: Value, private_key: Bytes, options: Option<ParseOptions>) -> Result<StateSignature, Error> {
+    let options = options.unwrap_or_default();
+    let state: State = parse_value(state, "state", &options)?;
     state.sign(private_key)
   }
 
-  fn recoverAddress(state: State, signature: Bytes) -> Result<String, &'static str> {
+  fn hashStates(states: Vec<Value>) -> Vec<Result<String, Error>> {
+    states
+      .into_par_iter()
+      .enumerate()
+      .map(|(i, state)| Ok(parse_state(state, i)?.hash().to_hex_string()))
+      .collect()
+  }
+
+  fn signStates(states: Vec<Value>, private_key: Bytes) -> Result<Vec<Result<StateSignature, Error>>, Error> {
+    let secret_key = parse_private_key(&private_key)?;
+    Ok(states
+      .into_par_iter()
+      .enumerate()
+      .map(|(i, state)| Ok(parse_state(state, i)?.sign_with_secret_key(&secret_key)))
+      .collect())
+  }
+
+  fn recoverAddresses(states: Vec<Value>, signatures: Vec<Bytes>) -> Result<Vec<Result<String, Error>>, Error> {
+    if states.len() != signatures.len() {
+      return Err(Error::BatchLengthMismatch { expected: states.len(), actual: signatures.len() });
+    }
+
+    Ok(states
+      .into_par_iter()
+      .zip(signatures.into_par_iter())
+      .enumerate()
+      .map(|(i, (state, signature))| parse_state(state, i)?.recover_address(signature))
+      .collect())
+  }
+
+  fn recoverAddress(state: Value, signature: Bytes, options: Option<ParseOptions>) -> Result<String, Error> {
+    let options = options.unwrap_or_default();
+    let state: State = parse_value(state, "state", &options)?;
     state.recover_address(signature)
   }
 
-  fn validatePeerUpdate(state: State, peer_update: State, peer_signature: Bytes) -> Result<Status, &'static str> {
-    state.validate_peer_update(peer_update, peer_signature)
+  fn verifySignature(hash: Bytes32, address: Address, signature: Bytes) -> Result<bool, Error> {
+    verify_signature(hash, address, signature)
+  }
+
+  fn toCompactSignature(signature: Bytes) -> Result<Bytes, Error> {
+    RecoverableSignature::from_slice(&signature).map(|signature| signature.to_compact())
+  }
+
+  fn fromCompactSignature(signature: Bytes) -> Result<RecoverableSignature, Error> {
+    RecoverableSignature::from_slice(&signature)
+  }
+
+  fn splitSignature(signature: RecoverableSignature) -> SplitSignature {
+    signature.split()
+  }
+
+  fn joinSignature(signature: RecoverableSignature) -> RecoverableSignature {
+    signature
+  }
+
+  fn signMessage(message: Bytes, private_key: Bytes) -> Result<RecoverableSignature, Error> {
+    let secret_key = parse_private_key(&private_key)?;
+    Ok(sign_message(&message, &secret_key))
+  }
+
+  fn recoverMessageSigner(message: Bytes, signature: Bytes) -> Result<String, Error> {
+    recover_message_signer(&message, &signature)
+  }
+
+  fn hashTypedData(typed_data: TypedData) -> Result<String, Error> {
+    typed_data.hash().map(|hash| hash.to_hex_string())
+  }
+
+  fn signTypedData(typed_data: TypedData, private_key: Bytes) -> Result<RecoverableSignature, Error> {
+    let secret_key = parse_private_key(&private_key)?;
+    typed_data.sign(&secret_key)
+  }
+
+  fn recoverTypedDataSigner(typed_data: TypedData, signature: Bytes) -> Result<String, Error> {
+    typed_data.recover_signer(&signature)
+  }
+
+  fn getAddressFromPrivateKey(private_key: Bytes) -> Result<String, Error> {
+    address_from_private_key(&private_key)
+  }
+
+  fn getAddressFromPublicKey(public_key: Bytes) -> Result<String, Error> {
+    address_from_public_key(&public_key)
+  }
+
+  fn deriveKeyFromSeed(seed: Bytes, index: u32) -> Result<DerivedKey, Error> {
+    derive_key_from_seed(&seed, index)
+  }
+
+  fn deriveKeyFromMnemonic(mnemonic: String, index: u32, passphrase: Option<String>) -> Result<DerivedKey, Error> {
+    derive_key_from_mnemonic(&mnemonic, &passphrase.unwrap_or_default(), index)
+  }
+
+  fn validatePeerUpdate(
+    state: Value,
+    peer_update: Value,
+    peer_signature: Bytes,
+    options: Option<PeerUpdateOptions>
+  ) -> Result<Status, Error> {
+    let options = options.unwrap_or_default();
+    let state: State = parse_value(state, "state", &options.parse)?;
+    let peer_update: State = parse_value(peer_update, "peerUpdate", &options.parse)?;
+    state.validate_peer_update_with_options(peer_update, peer_signature, &options.validation)
+  }
+
+  fn validateSupportProof(
+    channel: Value,
+    fixed_part: Value,
+    variable_parts: Value,
+    signatures: Vec<Bytes>,
+    who_signed_what: Vec<u8>,
+    options: Option<ParseOptions>
+  ) -> Result<Status, Error> {
+    let options = options.unwrap_or_default();
+    let channel: Channel = parse_value(channel, "channel", &options)?;
+    let fixed_part: FixedPart = parse_value(fixed_part, "fixedPart", &options)?;
+    let variable_parts: Vec<StateVariables> = parse_value(variable_parts, "variableParts", &options)?;
+    channel.validate_support_proof(fixed_part, variable_parts, signatures, who_signed_what)
+  }
+
+  fn registerApp(app_definition: Address, name: String) -> Result<(), Error> {
+    register_app(app_definition, builtin_app(&name)?);
+    Ok(())
+  }
+
+  fn unregisterApp(app_definition: Address) -> bool {
+    unregister_app(&app_definition)
+  }
+}
+
+fn throw_error<'a, C: Context<'a>, T>(cx: &mut C, error: Error) -> NeonResult<T> {
+  let js_error = JsError::error(cx, error.to_string())?;
+  let code = cx.string(error.code());
+  js_error.set(cx, "code", code)?;
+  cx.throw(js_error)
+}
+
+/// Deserializes a JS argument, throwing an `INVALID_INPUT` error that names the
+/// offending field, e.g. `state.outcome[0].allocationItems[2].amount`.
+fn parse_argument<'a, C: Context<'a>, T: DeserializeOwned>(
+  cx: &mut C,
+  value: Handle<'a, JsValue>,
+  name: &str,
+) -> NeonResult<T> {
+  parse_argument_with_options(cx, value, name, &ParseOptions::default())
+}
+
+fn parse_argument_with_options<'a, C: Context<'a>, T: DeserializeOwned>(
+  cx: &mut C,
+  value: Handle<'a, JsValue>,
+  name: &str,
+  options: &ParseOptions,
+) -> NeonResult<T> {
+  let value: Result<Value, _> = from_value(cx, value);
+  let result = value
+    .map_err(|error| Error::InvalidInput {
+      path: name.to_string(),
+      message: error.to_string(),
+    })
+    .and_then(|value| parse_value(value, name, options));
+
+  match result {
+    Ok(value) => Ok(value),
+    Err(error) => throw_error(cx, error),
+  }
+}
+
+/// Deserializes the state at index `i` of a batch, so that one malformed state
+/// fails only its own slot of the result.
+fn parse_state(state: Value, i: usize) -> Result<State, Error> {
+  parse_value(state, &format!("states[{}]", i), &ParseOptions::default())
+}
+
+/// Deserializes an optional options argument, defaulting when it is missing
+/// or `undefined`.
+fn parse_options<'a, T: DeserializeOwned + Default>(cx: &mut FunctionContext<'a>, index: i32) -> NeonResult<T> {
+  let options = match cx.argument_opt(index) {
+    Some(options) => options,
+    None => cx.undefined().upcast(),
+  };
+  let options: Option<T> = parse_argument(cx, options, "options")?;
+  Ok(options.unwrap_or_default())
+}
+
+fn camel_case(name: &str) -> String {
+  let mut words = name.split('_');
+  let mut result = words.next().unwrap_or_default().to_string();
+  for word in words {
+    let mut chars = word.chars();
+    if let Some(first) = chars.next() {
+      result.extend(first.to_uppercase());
+      result.push_str(chars.as_str());
+    }
+  }
+  result
+}
+
+struct SignStateTask {
+  state: State,
+  private_key: Bytes,
+}
+
+impl Task for SignStateTask {
+  type Output = StateSignature;
+  type Error = Error;
+  type JsEvent = JsValue;
+
+  fn perform(&self) -> Result<Self::Output, Self::Error> {
+    let secret_key = parse_private_key(&self.private_key)?;
+    Ok(self.state.sign_with_secret_key(&secret_key))
+  }
+
+  fn complete(self, mut cx: TaskContext, result: Result<Self::Output, Self::Error>) -> JsResult<Self::JsEvent> {
+    match result {
+      Ok(signature) => Ok(to_value(&mut cx, &signature)?),
+      Err(error) => throw_error(&mut cx, error),
+    }
+  }
+}
+
+fn sign_state_async(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+  let state = cx.argument::<JsValue>(0)?;
+  let private_key = cx.argument::<JsValue>(1)?;
+  let options: ParseOptions = parse_options(&mut cx, 2)?;
+  let callback = cx.argument::<JsFunction>(3)?;
+
+  SignStateTask {
+    state: parse_argument_with_options(&mut cx, state, "state", &options)?,
+    private_key: parse_argument(&mut cx, private_key, "privateKey")?,
   }
+  .schedule(callback);
+
+  Ok(cx.undefined())
 }
+
+struct RecoverAddressTask {
+  state: State,
+  signature: Bytes,
+}
+
+impl Task for RecoverAddressTask {
+  type Output = String;
+  type Error = Error;
+  type JsEvent = JsString;
+
+  fn perform(&self) -> Result<Self::Output, Self::Error> {
+    self.state.recover_address(self.signature.clone())
+  }
+
+  fn complete(self, mut cx: TaskContext, result: Result<Self::Output, Self::Error>) -> JsResult<Self::JsEvent> {
+    match result {
+      Ok(address) => Ok(cx.string(address)),
+      Err(error) => throw_error(&mut cx, error),
+    }
+  }
+}
+
+fn recover_address_async(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+  let state = cx.argument::<JsValue>(0)?;
+  let signature = cx.argument::<JsValue>(1)?;
+  let options: ParseOptions = parse_options(&mut cx, 2)?;
+  let callback = cx.argument::<JsFunction>(3)?;
+
+  RecoverAddressTask {
+    state: parse_argument_with_options(&mut cx, state, "state", &options)?,
+    signature: parse_argument(&mut cx, signature, "signature")?,
+  }
+  .schedule(callback);
+
+  Ok(cx.undefined())
+}
+
+struct ValidatePeerUpdateTask {
+  state: State,
+  peer_update: State,
+  peer_signature: Bytes,
+  options: ValidationOptions,
+}
+
+impl Task for ValidatePeerUpdateTask {
+  type Output = Status;
+  type Error = Error;
+  type JsEvent = JsValue;
+
+  fn perform(&self) -> Result<Self::Output, Self::Error> {
+    self
+      .state
+      .validate_peer_update_with_options(self.peer_update.clone(), self.peer_signature.clone(), &self.options)
+  }
+
+  fn complete(self, mut cx: TaskContext, result: Result<Self::Output, Self::Error>) -> JsResult<Self::JsEvent> {
+    match result {
+      Ok(status) => Ok(to_value(&mut cx, &status)?),
+      Err(error) => throw_error(&mut cx, error),
+    }
+  }
+}
+
+fn validate_peer_update_async(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+  let state = cx.argument::<JsValue>(0)?;
+  let peer_update = cx.argument::<JsValue>(1)?;
+  let peer_signature = cx.argument::<JsValue>(2)?;
+  let options: PeerUpdateOptions = parse_options(&mut cx, 3)?;
+  let callback = cx.argument::<JsFunction>(4)?;
+
+  ValidatePeerUpdateTask {
+    state: parse_argument_with_options(&mut cx, state, "state", &options.parse)?,
+    peer_update: parse_argument_with_options(&mut cx, peer_update, "peerUpdate", &options.parse)?,
+    peer_signature: parse_argument(&mut cx, peer_signature, "peerSignature")?,
+    options: options.validation,
+  }
+  .schedule(callback);
+
+  Ok(cx.undefined())
+}
+
+declare_types! {
+  pub class JsSigner for Signer {
+    init(mut cx) {
+      let private_key = cx.argument::<JsValue>(0)?;
+      let private_key: Bytes = parse_argument(&mut cx, private_key, "privateKey")?;
+      Signer::new(private_key).or_else(|error| throw_error(&mut cx, error))
+    }
+
+    method address(mut cx) {
+      let this = cx.this();
+      let address = cx.borrow(&this, |signer| signer.checksummed_address());
+      Ok(cx.string(address).upcast())
+    }
+
+    method signState(mut cx) {
+      let state = cx.argument::<JsValue>(0)?;
+      let state: State = parse_argument(&mut cx, state, "state")?;
+      let this = cx.this();
+      let signature = cx.borrow(&this, |signer| signer.sign_state(&state));
+      Ok(to_value(&mut cx, &signature)?)
+    }
+
+    method signMessage(mut cx) {
+      let message = cx.argument::<JsValue>(0)?;
+      let message: Bytes = parse_argument(&mut cx, message, "message")?;
+      let this = cx.this();
+      let signature = cx.borrow(&this, |signer| signer.sign_message(&message));
+      Ok(to_value(&mut cx, &signature)?)
+    }
+  }
+}
+
+register_module!(mut m, {
+  export_serde_functions(&mut m)?;
+
+  m.export_function("signStateAsync", sign_state_async)?;
+  m.export_function("recoverAddressAsync", recover_address_async)?;
+  m.export_function("validatePeerUpdateAsync", validate_peer_update_async)?;
+
+  m.export_class::<JsSigner>("Signer")?;
+
+  Ok(())
+});
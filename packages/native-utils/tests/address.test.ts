@@ -0,0 +1,40 @@
+import { utils } from 'ethers'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+const PRIVATE_KEY = '0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d'
+const ADDRESS = '0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1'
+
+describe('Address derivation', () => {
+  test('From private key', () => {
+    expect(utils.computeAddress(PRIVATE_KEY)).toStrictEqual(ADDRESS)
+    expect(native.getAddressFromPrivateKey(PRIVATE_KEY)).toStrictEqual(ADDRESS)
+    expect(wasm.getAddressFromPrivateKey(PRIVATE_KEY)).toStrictEqual(ADDRESS)
+  })
+
+  test('From uncompressed public key', () => {
+    const publicKey = utils.computePublicKey(PRIVATE_KEY, false)
+
+    expect(native.getAddressFromPublicKey(publicKey)).toStrictEqual(ADDRESS)
+    expect(wasm.getAddressFromPublicKey(publicKey)).toStrictEqual(ADDRESS)
+  })
+
+  test('From compressed public key', () => {
+    const publicKey = utils.computePublicKey(PRIVATE_KEY, true)
+
+    expect(native.getAddressFromPublicKey(publicKey)).toStrictEqual(ADDRESS)
+    expect(wasm.getAddressFromPublicKey(publicKey)).toStrictEqual(ADDRESS)
+  })
+
+  test('Catches invalid keys', () => {
+    expect(() => native.getAddressFromPrivateKey('0x00')).toThrow('invalid private key')
+    expect(() => wasm.getAddressFromPrivateKey('0x00')).toThrow('invalid private key')
+
+    expect(() => native.getAddressFromPublicKey('0x00')).toThrow('invalid public key')
+    expect(() => wasm.getAddressFromPublicKey('0x00')).toThrow('invalid public key')
+
+    const notOnCurve = '0x02' + 'ff'.repeat(32)
+    expect(() => native.getAddressFromPublicKey(notOnCurve)).toThrow('invalid public key')
+    expect(() => wasm.getAddressFromPublicKey(notOnCurve)).toThrow('invalid public key')
+  })
+})
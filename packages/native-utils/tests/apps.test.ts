@@ -0,0 +1,99 @@
+import { State } from '@statechannels/nitro-protocol'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+import { CURRENT_STATE as FIXTURE_STATE, Lib, PRIVATE_KEY1 } from './fixtures'
+
+const SINGLE_ASSET_PAYMENTS = '0x5ad20c7fd4e3d8a1a6c0b6f4d0f6a1f02b1b9c5e'
+
+const DESTINATION1 = '0x00000000000000000000000063FaC9201494f0bd17B9892B9fae4d52fe3BD377'
+const DESTINATION2 = '0x00000000000000000000000090F8bf6A479f320ead074411a4B0e7944Ea8c9C1'
+
+function allocation(amount1: string, amount2: string) {
+  return [
+    {
+      assetHolderAddress: '0x0000000000000000000000000000000000000000',
+      allocationItems: [
+        { destination: DESTINATION1, amount: amount1 },
+        { destination: DESTINATION2, amount: amount2 },
+      ],
+    },
+  ]
+}
+
+const CURRENT_STATE: State = {
+  ...FIXTURE_STATE,
+  outcome: allocation('5', '5'),
+  appDefinition: SINGLE_ASSET_PAYMENTS,
+  appData: '0x',
+}
+
+function validatePeerUpdate(utils: Lib, outcome: State['outcome']) {
+  const peerUpdate = { ...CURRENT_STATE, turnNum: 6, outcome }
+  const { signature } = native.signState(peerUpdate, PRIVATE_KEY1)
+  return utils.validatePeerUpdate(CURRENT_STATE, peerUpdate, signature)
+}
+
+describe('SingleAssetPayments', () => {
+  beforeAll(() => {
+    native.registerApp(SINGLE_ASSET_PAYMENTS, 'SingleAssetPayments')
+    wasm.registerApp(SINGLE_ASSET_PAYMENTS, 'SingleAssetPayments')
+  })
+
+  afterAll(() => {
+    native.unregisterApp(SINGLE_ASSET_PAYMENTS)
+    wasm.unregisterApp(SINGLE_ASSET_PAYMENTS)
+  })
+
+  // Participant 0 moves in turn 6, like on chain.
+  test('Mover may pay the other participant', () => {
+    for (const utils of [native, wasm]) {
+      expect(validatePeerUpdate(utils, allocation('3', '7'))).toEqual('True')
+    }
+  })
+
+  test('Mover may not take from the other participant', () => {
+    for (const utils of [native, wasm]) {
+      expect(() => validatePeerUpdate(utils, allocation('7', '3'))).toThrow(
+        'Nonmover balance decreased: allocation item 1',
+      )
+    }
+  })
+
+  test('Total must be conserved', () => {
+    for (const utils of [native, wasm]) {
+      expect(() => validatePeerUpdate(utils, allocation('4', '5'))).toThrow(
+        'Total allocated cannot change',
+      )
+    }
+  })
+
+  test('Guarantees and multiple assets are rejected', () => {
+    const guarantee = [
+      {
+        assetHolderAddress: '0x0000000000000000000000000000000000000000',
+        guarantee: {
+          targetChannelId: '0x0000000000000000000000000000000000000000000000000000000000000000',
+          destinations: [DESTINATION1, DESTINATION2],
+        },
+      },
+    ]
+
+    for (const utils of [native, wasm]) {
+      expect(() => validatePeerUpdate(utils, guarantee)).toThrow(
+        'AssetOutcomeType must be Allocation',
+      )
+      expect(() =>
+        validatePeerUpdate(utils, [...allocation('5', '5'), ...allocation('5', '5')]),
+      ).toThrow('Only one asset allowed')
+    }
+  })
+
+  test('Unknown apps cannot be registered', () => {
+    expect(() => native.registerApp(SINGLE_ASSET_PAYMENTS, 'UnknownApp' as any)).toThrow(
+      'unknown app: UnknownApp',
+    )
+    expect(() => wasm.registerApp(SINGLE_ASSET_PAYMENTS, 'UnknownApp' as any)).toThrow(
+      'unknown app: UnknownApp',
+    )
+  })
+})
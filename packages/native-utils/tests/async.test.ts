@@ -0,0 +1,94 @@
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+import { CURRENT_STATE, NEXT_STATE, PRIVATE_KEY1, PRIVATE_KEY2 } from './fixtures'
+
+describe('Async variants', () => {
+  test('signStateAsync matches signState', async () => {
+    const signed = await native.signStateAsync(NEXT_STATE, PRIVATE_KEY2)
+
+    expect(signed).toStrictEqual(native.signState(NEXT_STATE, PRIVATE_KEY2))
+  })
+
+  test('signStateAsync rejects an invalid private key', async () => {
+    await expect(native.signStateAsync(NEXT_STATE, '0x00')).rejects.toThrow('invalid private key')
+    await expect(native.signStateAsync(NEXT_STATE, '0x00')).rejects.toHaveProperty('code', 'INVALID_PRIVATE_KEY')
+  })
+
+  test('recoverAddressAsync matches recoverAddress', async () => {
+    const { signature } = native.signState(NEXT_STATE, PRIVATE_KEY1)
+
+    expect(await native.recoverAddressAsync(NEXT_STATE, signature)).toStrictEqual(
+      native.recoverAddress(NEXT_STATE, signature),
+    )
+    await expect(native.recoverAddressAsync(NEXT_STATE, '0x00')).rejects.toHaveProperty(
+      'code',
+      'INVALID_SIGNATURE_LENGTH',
+    )
+  })
+
+  test('validatePeerUpdateAsync matches validatePeerUpdate', async () => {
+    const { signature } = native.signState(NEXT_STATE, PRIVATE_KEY1)
+
+    expect(await native.validatePeerUpdateAsync(CURRENT_STATE, NEXT_STATE, signature)).toEqual('NeedToCheckApp')
+
+    const { signature: wrongSignature } = native.signState(NEXT_STATE, PRIVATE_KEY2)
+    await expect(native.validatePeerUpdateAsync(CURRENT_STATE, NEXT_STATE, wrongSignature)).rejects.toThrow(
+      'Signature verification failed',
+    )
+  })
+
+  test('Options are honoured like in the sync variants', async () => {
+    // A participant address with a broken EIP-55 checksum.
+    const badChecksum = {
+      ...NEXT_STATE,
+      channel: {
+        ...NEXT_STATE.channel,
+        participants: [NEXT_STATE.channel.participants[0], '0x90f8bf6A479f320ead074411a4B0e7944Ea8c9C1'],
+      },
+    }
+    const strict = { strictChecksums: true }
+    const { signature } = native.signState(NEXT_STATE, PRIVATE_KEY1)
+
+    // The same destination twice, which only `validateOutcome` rejects.
+    const destination = '0x00000000000000000000000063fac9201494f0bd17b9892b9fae4d52fe3bd377'
+    const outcome = [
+      {
+        assetHolderAddress: '0x0000000000000000000000000000000000000000',
+        allocationItems: [
+          { destination, amount: '0x01' },
+          { destination, amount: '0x02' },
+        ],
+      },
+    ]
+    const current = { ...CURRENT_STATE, outcome }
+    const next = { ...NEXT_STATE, outcome }
+    const { signature: nextSignature } = native.signState(next, PRIVATE_KEY1)
+
+    for (const lib of [native, wasm]) {
+      await expect(lib.signStateAsync(badChecksum, PRIVATE_KEY2, strict)).rejects.toHaveProperty(
+        'code',
+        'INVALID_ADDRESS_CHECKSUM',
+      )
+      await expect(lib.recoverAddressAsync(badChecksum, signature, strict)).rejects.toHaveProperty(
+        'code',
+        'INVALID_ADDRESS_CHECKSUM',
+      )
+      await expect(lib.validatePeerUpdateAsync(CURRENT_STATE, badChecksum, signature, strict)).rejects.toHaveProperty(
+        'code',
+        'INVALID_ADDRESS_CHECKSUM',
+      )
+
+      expect(await lib.validatePeerUpdateAsync(current, next, nextSignature)).toEqual('NeedToCheckApp')
+      await expect(
+        lib.validatePeerUpdateAsync(current, next, nextSignature, { validateOutcome: true }),
+      ).rejects.toHaveProperty('code', 'INVALID_OUTCOME')
+    }
+  })
+
+  test('Runs many tasks concurrently', async () => {
+    const states = Array.from({ length: 32 }, (_, i) => ({ ...CURRENT_STATE, turnNum: i }))
+    const signed = await Promise.all(states.map(state => native.signStateAsync(state, PRIVATE_KEY1)))
+
+    expect(signed).toStrictEqual(states.map(state => native.signState(state, PRIVATE_KEY1)))
+  })
+})
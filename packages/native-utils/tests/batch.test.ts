@@ -0,0 +1,93 @@
+import { State } from '@statechannels/nitro-protocol'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+const DEFAULT_STATE: State = {
+  turnNum: 1,
+  isFinal: false,
+  channel: {
+    chainId: '1',
+    channelNonce: 1,
+    participants: ['0x19E7E376E7C213B7E7e7e46cc70A5dD086DAff2A'],
+  },
+  challengeDuration: 1,
+  outcome: [],
+  appDefinition: '0x0000000000000000000000000000000000000000',
+  appData: '0x0000000000000000000000000000000000000000000000000000000000000000',
+}
+
+const STATES: State[] = [1, 2, 3, 4, 5].map(turnNum => ({ ...DEFAULT_STATE, turnNum }))
+
+const PRIVATE_KEY = '0x1111111111111111111111111111111111111111111111111111111111111111'
+const ADDRESS = '0x19E7E376E7C213B7E7e7e46cc70A5dD086DAff2A'
+
+const SIGNATURES = STATES.map(state => native.signState(state, PRIVATE_KEY).signature)
+
+describe('Batch APIs', () => {
+  test('hashStates', () => {
+    const hashes = STATES.map(state => native.hashState(state))
+
+    expect(native.hashStates(STATES)).toStrictEqual(hashes)
+    expect(wasm.hashStates(STATES)).toStrictEqual(hashes)
+  })
+
+  test('signStates', () => {
+    const signedStates = STATES.map(state => native.signState(state, PRIVATE_KEY))
+
+    expect(native.signStates(STATES, PRIVATE_KEY)).toStrictEqual(signedStates)
+    expect(wasm.signStates(STATES, PRIVATE_KEY)).toStrictEqual(
+      signedStates.map(({ hash, signature }) => ({ hash, signature })),
+    )
+
+    expect(() => native.signStates(STATES, '0x00')).toThrow('invalid private key')
+    expect(() => wasm.signStates(STATES, '0x00')).toThrow('invalid private key')
+  })
+
+  test('recoverAddresses', () => {
+    expect(native.recoverAddresses(STATES, SIGNATURES)).toStrictEqual(STATES.map(() => ADDRESS))
+    expect(wasm.recoverAddresses(STATES, SIGNATURES)).toStrictEqual(STATES.map(() => ADDRESS))
+  })
+
+  test('recoverAddresses reports errors per item', () => {
+    const signatures = [...SIGNATURES]
+    signatures[2] = '0x00'
+
+    for (const utils of [native, wasm]) {
+      const addresses = utils.recoverAddresses(STATES, signatures)
+
+      expect(addresses[1]).toStrictEqual(ADDRESS)
+      expect(addresses[2]).toHaveProperty('code', 'INVALID_SIGNATURE_LENGTH')
+      expect(addresses[3]).toStrictEqual(ADDRESS)
+
+      expect(() => utils.recoverAddresses(STATES, signatures.slice(1))).toThrow(
+        'expected one signature per state',
+      )
+    }
+  })
+
+  test('a malformed state fails only its own slot', () => {
+    const states = [...STATES]
+    states[2] = { ...DEFAULT_STATE, turnNum: 'three' as any }
+    const hashes = STATES.map(state => native.hashState(state))
+
+    for (const utils of [native, wasm]) {
+      const results = [
+        utils.hashStates(states),
+        utils.signStates(states, PRIVATE_KEY),
+        utils.recoverAddresses(states, SIGNATURES),
+      ]
+
+      for (const result of results) {
+        expect(result).toHaveLength(STATES.length)
+        expect(result[2]).toHaveProperty('code', 'INVALID_INPUT')
+        expect(result[2]).toHaveProperty('message', expect.stringContaining('states[2].turnNum'))
+      }
+
+      expect(results[0][1]).toStrictEqual(hashes[1])
+      expect(results[0][3]).toStrictEqual(hashes[3])
+      expect(results[1][3]).toHaveProperty('signature', SIGNATURES[3])
+      expect(results[2][1]).toStrictEqual(ADDRESS)
+      expect(results[2][3]).toStrictEqual(ADDRESS)
+    }
+  })
+})
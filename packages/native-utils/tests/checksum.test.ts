@@ -0,0 +1,90 @@
+import { State } from '@statechannels/nitro-protocol'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+const STATE: State = {
+  turnNum: 1,
+  isFinal: false,
+  channel: {
+    chainId: '1',
+    channelNonce: 1,
+    participants: ['0x63FaC9201494f0bd17B9892B9fae4d52fe3BD377', '0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1'],
+  },
+  challengeDuration: 1,
+  outcome: [
+    {
+      assetHolderAddress: '0x4ad3f07bee9e0d3d4e0a9bb8a7f6f5f1f2c8a8a6',
+      allocationItems: [],
+    },
+  ],
+  appDefinition: '0x0000000000000000000000000000000000000000',
+  appData: '0x',
+}
+
+// The second participant with the casing of its first letter flipped.
+const TYPO = '0x90f8bf6A479f320ead074411a4B0e7944Ea8c9C1'
+
+const STRICT = { strictChecksums: true }
+
+describe('Strict checksums', () => {
+  test('Accepts checksummed and lowercase addresses', () => {
+    const hash = native.hashState(STATE)
+
+    expect(native.hashState(STATE, STRICT)).toStrictEqual(hash)
+    expect(wasm.hashState(STATE, STRICT)).toStrictEqual(hash)
+  })
+
+  test('Accepts bad checksums by default', () => {
+    const state = { ...STATE, channel: { ...STATE.channel, participants: [STATE.channel.participants[0], TYPO] } }
+
+    expect(native.hashState(state)).toStrictEqual(native.hashState(STATE))
+    expect(wasm.hashState(state)).toStrictEqual(native.hashState(STATE))
+  })
+
+  test('Rejects bad checksums in strict mode', () => {
+    const state = { ...STATE, channel: { ...STATE.channel, participants: [STATE.channel.participants[0], TYPO] } }
+    const message = `invalid address checksum at state.channel.participants[1]: got ${TYPO}, expected ${STATE.channel.participants[1]}`
+
+    expect(() => native.hashState(state, STRICT)).toThrow(message)
+    expect(() => wasm.hashState(state, STRICT)).toThrow(message)
+
+    try {
+      native.signState(state, '0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d', STRICT)
+      fail('expected an error')
+    } catch (error) {
+      expect(error.code).toStrictEqual('INVALID_ADDRESS_CHECKSUM')
+    }
+  })
+
+  test('Names the offending field', () => {
+    const outcome = [{ ...STATE.outcome[0], assetHolderAddress: TYPO }]
+
+    expect(() => native.hashState({ ...STATE, outcome }, STRICT)).toThrow(
+      'invalid address checksum at state.outcome[0].assetHolderAddress',
+    )
+    expect(() => wasm.hashState({ ...STATE, appDefinition: TYPO }, STRICT)).toThrow(
+      'invalid address checksum at state.appDefinition',
+    )
+    expect(() =>
+      native.validatePeerUpdate(STATE, { ...STATE, turnNum: 2, appDefinition: TYPO }, '0x', STRICT),
+    ).toThrow('invalid address checksum at peerUpdate.appDefinition')
+  })
+
+  test('Checks the variable parts of support proofs', () => {
+    const fixedPart = { appDefinition: STATE.appDefinition, challengeDuration: STATE.challengeDuration }
+    const variableParts = [
+      {
+        turnNum: STATE.turnNum,
+        isFinal: STATE.isFinal,
+        outcome: [{ ...STATE.outcome[0], assetHolderAddress: TYPO }],
+        appData: STATE.appData,
+      },
+    ]
+
+    for (const lib of [native, wasm]) {
+      expect(() =>
+        lib.validateSupportProof(STATE.channel, fixedPart, variableParts, ['0x', '0x'], [0, 0], STRICT),
+      ).toThrow('invalid address checksum at variableParts[0].outcome[0].assetHolderAddress')
+    }
+  })
+})
@@ -0,0 +1,34 @@
+import { describeBindings, CURRENT_STATE, NEXT_STATE, PRIVATE_KEY1 } from './fixtures'
+
+// The example from EIP-2098.
+const SIGNATURE =
+  '0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b907e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea520641c'
+const COMPACT =
+  '0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90fe865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064'
+
+describe('Compact signatures', () => {
+  describeBindings(lib => {
+    test('Converts the EIP-2098 example', () => {
+      expect(lib.toCompactSignature(SIGNATURE)).toStrictEqual(COMPACT)
+      expect(lib.fromCompactSignature(COMPACT)).toStrictEqual(SIGNATURE)
+      expect(lib.fromCompactSignature(SIGNATURE)).toStrictEqual(SIGNATURE)
+    })
+
+    test('Recovers and validates compact signatures', () => {
+      const { signature } = lib.signState(NEXT_STATE, PRIVATE_KEY1)
+      const compact = lib.toCompactSignature(signature)
+
+      expect(lib.recoverAddress(NEXT_STATE, compact)).toStrictEqual(lib.recoverAddress(NEXT_STATE, signature))
+      expect(lib.validatePeerUpdate(CURRENT_STATE, NEXT_STATE, compact)).toStrictEqual('NeedToCheckApp')
+    })
+
+    test('Rejects signatures of the wrong length', () => {
+      expect(() => lib.fromCompactSignature('0x')).toThrow(
+        'invalid signature length: expected 64 or 65 bytes, got 0',
+      )
+      expect(() => lib.toCompactSignature('0x00')).toThrow(
+        expect.objectContaining({ code: 'INVALID_SIGNATURE_LENGTH' }),
+      )
+    })
+  })
+})
@@ -0,0 +1,74 @@
+import * as nitro from '@statechannels/nitro-protocol'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+const DEFAULT_STATE: nitro.State = {
+  turnNum: 1,
+  isFinal: false,
+  channel: {
+    chainId: '1',
+    channelNonce: 1,
+    participants: [],
+  },
+  challengeDuration: 1,
+  outcome: [],
+  appDefinition: '0x0000000000000000000000000000000000000000',
+  appData: '0x00',
+}
+
+const ALLOCATION_OUTCOME: nitro.Outcome = [
+  {
+    assetHolderAddress: '0x19E7E376E7C213B7E7e7e46cc70A5dD086DAff2A',
+    allocationItems: [
+      {
+        destination: '0x0000000000000000000000000000000000000000000000000000000000000001',
+        amount: '0x0a',
+      },
+      {
+        destination: '0x0000000000000000000000000000000000000000000000000000000000000002',
+        amount: '0x0de0b6b3a7640000',
+      },
+    ],
+  },
+]
+
+const GUARANTEE_OUTCOME: nitro.Outcome = [
+  {
+    assetHolderAddress: '0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1',
+    guarantee: {
+      targetChannelId: '0x1111111111111111111111111111111111111111111111111111111111111111',
+      destinations: [
+        '0x0000000000000000000000000000000000000000000000000000000000000001',
+        '0x0000000000000000000000000000000000000000000000000000000000000002',
+      ],
+    },
+  },
+]
+
+describe('decodeOutcome', () => {
+  test('Empty outcome', () => {
+    const encoded = native.encodeOutcome(DEFAULT_STATE)
+
+    expect(native.decodeOutcome(encoded)).toStrictEqual([])
+    expect(wasm.decodeOutcome(encoded)).toStrictEqual([])
+  })
+
+  test('Allocation and guarantee outcomes', () => {
+    for (const outcome of [
+      ALLOCATION_OUTCOME,
+      GUARANTEE_OUTCOME,
+      [...ALLOCATION_OUTCOME, ...GUARANTEE_OUTCOME],
+    ]) {
+      const encoded = nitro.encodeOutcome(outcome)
+
+      expect(native.decodeOutcome(encoded)).toStrictEqual(nitro.decodeOutcome(encoded))
+      expect(wasm.decodeOutcome(encoded)).toStrictEqual(nitro.decodeOutcome(encoded))
+      expect(native.decodeOutcome(encoded)).toStrictEqual(outcome)
+    }
+  })
+
+  test('Catches invalid encodings', () => {
+    expect(() => native.decodeOutcome('0x010203')).toThrow('invalid ABI encoding')
+    expect(() => wasm.decodeOutcome('0x010203')).toThrow('invalid ABI encoding')
+  })
+})
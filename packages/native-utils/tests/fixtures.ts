@@ -0,0 +1,49 @@
+import { State } from '@statechannels/nitro-protocol'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+export type Lib = typeof native | typeof wasm
+
+/**
+ * The private key of participant 0 of `CHANNEL`.
+ */
+export const PRIVATE_KEY1 = '0x8da4ef21b864d2cc526dbdb2a120bd2874c36c9d0a1fb7f8c63d7f7a8b41de8f'
+
+/**
+ * The private key of participant 1 of `CHANNEL`.
+ */
+export const PRIVATE_KEY2 = '0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d'
+
+export const CHANNEL = {
+  chainId: '1',
+  channelNonce: 1,
+  participants: ['0x63FaC9201494f0bd17B9892B9fae4d52fe3BD377', '0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1'],
+}
+
+export const CURRENT_STATE: State = {
+  turnNum: 5,
+  isFinal: false,
+  channel: CHANNEL,
+  challengeDuration: 1,
+  outcome: [],
+  appDefinition: '0x0000000000000000000000000000000000000000',
+  appData: '0x0000000000000000000000000000000000000000000000000000000000000000',
+}
+
+export const NEXT_STATE: State = {
+  ...CURRENT_STATE,
+  turnNum: 6,
+}
+
+/**
+ * Declares the tests added by `fn` once for the native and once for the wasm
+ * bindings, each in its own `describe` block.
+ */
+export function describeBindings(fn: (lib: Lib) => void): void {
+  for (const [name, lib] of [
+    ['native', native],
+    ['wasm', wasm],
+  ] as const) {
+    describe(name, () => fn(lib))
+  }
+}
@@ -0,0 +1,53 @@
+import { State } from '@statechannels/nitro-protocol'
+import * as native from '..'
+import { describeBindings, CURRENT_STATE, PRIVATE_KEY1 } from './fixtures'
+
+const STATE: State = {
+  ...CURRENT_STATE,
+  turnNum: 1,
+  outcome: [
+    {
+      assetHolderAddress: '0x4ad3f07bee9e0d3d4e0a9bb8a7f6f5f1f2c8a8a6',
+      allocationItems: [
+        { destination: '0x00000000000000000000000063fac9201494f0bd17b9892b9fae4d52fe3bd377', amount: '1' },
+        { destination: '0x00000000000000000000000090f8bf6a479f320ead074411a4b0e7944ea8c9c1', amount: -1 },
+      ],
+    },
+  ],
+} as any
+
+describe('Invalid input', () => {
+  describeBindings(lib => {
+    test('Reports the path of the offending field', () => {
+      const message = 'state.outcome[0].allocationItems[1].amount: negative integer'
+
+      for (const fn of [
+        () => lib.hashState(STATE),
+        () => lib.hashOutcome(STATE),
+        () => lib.signState(STATE, PRIVATE_KEY1),
+      ]) {
+        expect(fn).toThrow(message)
+        expect(fn).toThrow(expect.objectContaining({ code: 'INVALID_INPUT' }))
+      }
+    })
+
+    test('Reports missing fields', () => {
+      const { turnNum, ...state } = STATE
+
+      expect(() => lib.hashAppPart(state as any)).toThrow('state: missing field `turnNum`')
+    })
+
+    test('Rejects malformed hex strings', () => {
+      expect(() => lib.hashMessage('0xzz')).toThrow(expect.objectContaining({ code: 'INVALID_INPUT' }))
+      expect(() => lib.getAddressFromPrivateKey('0x123')).toThrow(/^privateKey: /)
+      expect(() => lib.recoverMessageSigner('0x', 42 as any)).toThrow(/^signature: /)
+    })
+  })
+
+  test('Rejects invalid arguments of async variants', async () => {
+    await expect(native.signStateAsync(STATE, PRIVATE_KEY1)).rejects.toHaveProperty('code', 'INVALID_INPUT')
+    await expect(native.recoverAddressAsync(STATE, '0x')).rejects.toThrow(
+      'state.outcome[0].allocationItems[1].amount: negative integer',
+    )
+  })
+})
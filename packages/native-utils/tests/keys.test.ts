@@ -0,0 +1,50 @@
+import { Wallet, utils } from 'ethers'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+const MNEMONIC = 'test test test test test test test test test test test junk'
+
+describe('Key derivation', () => {
+  test('Derives keys from a mnemonic like ethers', () => {
+    for (let i = 0; i < 3; i++) {
+      const wallet = Wallet.fromMnemonic(MNEMONIC, `m/44'/60'/0'/0/${i}`)
+      const expected = { privateKey: wallet.privateKey, address: wallet.address }
+
+      expect(native.deriveKeyFromMnemonic(MNEMONIC, i)).toStrictEqual(expected)
+      expect(wasm.deriveKeyFromMnemonic(MNEMONIC, i)).toStrictEqual(expected)
+    }
+  })
+
+  test('Supports a passphrase', () => {
+    const seed = utils.mnemonicToSeed(MNEMONIC, 'secret')
+    const node = utils.HDNode.fromSeed(seed).derivePath("m/44'/60'/0'/0/0")
+    const expected = { privateKey: node.privateKey, address: node.address }
+
+    expect(native.deriveKeyFromMnemonic(MNEMONIC, 0, 'secret')).toStrictEqual(expected)
+    expect(wasm.deriveKeyFromMnemonic(MNEMONIC, 0, 'secret')).toStrictEqual(expected)
+  })
+
+  test('Derives keys from a seed like ethers', () => {
+    const seed = '0x000102030405060708090a0b0c0d0e0f'
+    const node = utils.HDNode.fromSeed(seed).derivePath("m/44'/60'/0'/0/7")
+    const expected = { privateKey: node.privateKey, address: node.address }
+
+    expect(native.deriveKeyFromSeed(seed, 7)).toStrictEqual(expected)
+    expect(wasm.deriveKeyFromSeed(seed, 7)).toStrictEqual(expected)
+  })
+
+  test('Derived keys sign states', () => {
+    const { privateKey, address } = native.deriveKeyFromMnemonic(MNEMONIC, 0)
+
+    expect(new native.Signer(privateKey).address).toStrictEqual(address)
+  })
+
+  test('Catches invalid input', () => {
+    expect(() => native.deriveKeyFromSeed('0x00', 0)).toThrow('invalid seed length')
+    expect(() => wasm.deriveKeyFromSeed('0x00', 0)).toThrow('invalid seed length')
+
+    const invalidMnemonic = 'test test test test test test test test test test test test'
+    expect(() => native.deriveKeyFromMnemonic(invalidMnemonic, 0)).toThrow('invalid mnemonic')
+    expect(() => wasm.deriveKeyFromMnemonic(invalidMnemonic, 0)).toThrow('invalid mnemonic')
+  })
+})
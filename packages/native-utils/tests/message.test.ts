@@ -0,0 +1,35 @@
+import { Wallet, utils } from 'ethers'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+const PRIVATE_KEY = '0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d'
+const ADDRESS = '0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1'
+
+const MESSAGE = utils.hexlify(utils.toUtf8Bytes('I own 0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1'))
+
+describe('Sign message', () => {
+  test('Signs messages like ethers', async () => {
+    const expected = await new Wallet(PRIVATE_KEY).signMessage(utils.arrayify(MESSAGE))
+
+    expect(native.signMessage(MESSAGE, PRIVATE_KEY)).toStrictEqual(expected)
+    expect(wasm.signMessage(MESSAGE, PRIVATE_KEY)).toStrictEqual(expected)
+    expect(new native.Signer(PRIVATE_KEY).signMessage(MESSAGE)).toStrictEqual(expected)
+    expect(new wasm.Signer(PRIVATE_KEY).signMessage(MESSAGE)).toStrictEqual(expected)
+  })
+
+  test('Recovers message signers', async () => {
+    const signature = await new Wallet(PRIVATE_KEY).signMessage(utils.arrayify(MESSAGE))
+
+    expect(utils.verifyMessage(utils.arrayify(MESSAGE), signature)).toStrictEqual(ADDRESS)
+    expect(native.recoverMessageSigner(MESSAGE, signature)).toStrictEqual(ADDRESS)
+    expect(wasm.recoverMessageSigner(MESSAGE, signature)).toStrictEqual(ADDRESS)
+  })
+
+  test('Catches invalid input', () => {
+    expect(() => native.signMessage(MESSAGE, '0x00')).toThrow('invalid private key')
+    expect(() => wasm.signMessage(MESSAGE, '0x00')).toThrow('invalid private key')
+
+    expect(() => native.recoverMessageSigner(MESSAGE, '0x00')).toThrow('invalid signature length')
+    expect(() => wasm.recoverMessageSigner(MESSAGE, '0x00')).toThrow('invalid signature length')
+  })
+})
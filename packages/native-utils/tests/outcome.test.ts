@@ -0,0 +1,43 @@
+import { State } from '@statechannels/nitro-protocol'
+import { describeBindings, CURRENT_STATE as FIXTURE_STATE, PRIVATE_KEY1 } from './fixtures'
+
+const ASSET_HOLDER = '0x4ad3f07bee9e0d3d4e0a9bb8a7f6f5f1f2c8a8a6'
+const ALICE = '0x00000000000000000000000063fac9201494f0bd17b9892b9fae4d52fe3bd377'
+
+const CURRENT_STATE: State = {
+  ...FIXTURE_STATE,
+  outcome: [
+    {
+      assetHolderAddress: ASSET_HOLDER,
+      allocationItems: [
+        { destination: ALICE, amount: '1' },
+        { destination: ALICE, amount: '2' },
+      ],
+    },
+  ],
+}
+
+const NEXT_STATE: State = {
+  ...CURRENT_STATE,
+  turnNum: 6,
+}
+
+describe('Outcome validation', () => {
+  describeBindings(lib => {
+    test('Rejects invalid outcomes only when asked to', () => {
+      const { signature } = lib.signState(NEXT_STATE, PRIVATE_KEY1)
+
+      // Outcomes are only validated when asked to.
+      expect(lib.validatePeerUpdate(CURRENT_STATE, NEXT_STATE, signature)).toStrictEqual('NeedToCheckApp')
+      expect(lib.validatePeerUpdate(CURRENT_STATE, NEXT_STATE, signature, { strictChecksums: true })).toStrictEqual(
+        'NeedToCheckApp',
+      )
+
+      const validate = () => lib.validatePeerUpdate(CURRENT_STATE, NEXT_STATE, signature, { validateOutcome: true })
+      expect(validate).toThrow(
+        new RegExp(`^invalid outcome: duplicate destination ${ALICE} for asset holder ${ASSET_HOLDER}$`, 'i'),
+      )
+      expect(validate).toThrow(expect.objectContaining({ code: 'INVALID_OUTCOME' }))
+    })
+  })
+})
@@ -0,0 +1,92 @@
+import { AllocationAssetOutcome, Guarantee } from '@statechannels/nitro-protocol'
+import { describeBindings } from './fixtures'
+
+const destination = (i: number) => '0x' + i.toString(16).padStart(64, '0')
+
+const OUTCOME: AllocationAssetOutcome = {
+  assetHolderAddress: '0x4ad3f07bee9e0d3d4e0a9bb8a7f6f5f1f2c8a8a6',
+  allocationItems: [
+    { destination: destination(1), amount: '0x05' },
+    { destination: destination(2), amount: '0x03' },
+    { destination: destination(3), amount: '0x02' },
+  ],
+}
+
+describe('Transfer payouts', () => {
+  describeBindings(lib => {
+    test('Pays out everything when fully funded', () => {
+      expect(lib.computeTransferPayouts(OUTCOME, '10')).toStrictEqual({
+        newAllocation: OUTCOME.allocationItems.map(({ destination }) => ({ destination, amount: '0x00' })),
+        payouts: ['0x05', '0x03', '0x02'],
+        totalPayouts: '0x0a',
+        safeToDelete: true,
+      })
+    })
+
+    test('Pays out in priority order when partially funded', () => {
+      expect(lib.computeTransferPayouts(OUTCOME, '0x06', [1, 2])).toStrictEqual({
+        newAllocation: [
+          { destination: destination(1), amount: '0x05' },
+          { destination: destination(2), amount: '0x02' },
+          { destination: destination(3), amount: '0x02' },
+        ],
+        payouts: ['0x01', '0x00'],
+        totalPayouts: '0x01',
+        safeToDelete: false,
+      })
+    })
+
+    test('Rejects invalid indices', () => {
+      expect(() => lib.computeTransferPayouts(OUTCOME, '10', [1, 0])).toThrow(
+        expect.objectContaining({ code: 'INDICES_NOT_INCREASING' }),
+      )
+      expect(() => lib.computeTransferPayouts(OUTCOME, '10', [3])).toThrow('index out of range: 3 >= 3')
+    })
+  })
+})
+
+describe('Claim payouts', () => {
+  const guarantee: Guarantee = {
+    targetChannelId: destination(0xff),
+    destinations: [destination(3), destination(1)],
+  }
+
+  describeBindings(lib => {
+    test('Matches the AssetHolder', () => {
+      expect(lib.computeClaimPayouts(guarantee, OUTCOME, '0x06')).toStrictEqual({
+        newAllocation: [
+          { destination: destination(1), amount: '0x01' },
+          { destination: destination(2), amount: '0x03' },
+        ],
+        payouts: ['0x04', '0x00', '0x02'],
+        totalPayouts: '0x06',
+        remainingHoldings: '0x00',
+      })
+    })
+  })
+})
+
+describe('Deposit safety', () => {
+  describeBindings(lib => {
+    test('Matches the AssetHolder', () => {
+      expect(lib.computeDepositSafety(OUTCOME, destination(1), '0x00')).toStrictEqual({
+        expectedHeld: '0x00',
+        amountRequired: '0x05',
+        safeToDeposit: true,
+      })
+      expect(lib.computeDepositSafety(OUTCOME, destination(2), '0x04')).toStrictEqual({
+        expectedHeld: '0x05',
+        amountRequired: '0x03',
+        safeToDeposit: false,
+      })
+      expect(lib.computeDepositSafety(OUTCOME, destination(3), '0x09')).toStrictEqual({
+        expectedHeld: '0x08',
+        amountRequired: '0x01',
+        safeToDeposit: true,
+      })
+      expect(() => lib.computeDepositSafety(OUTCOME, destination(4), '0x00')).toThrow(
+        expect.objectContaining({ code: 'DESTINATION_NOT_FOUND' }),
+      )
+    })
+  })
+})
@@ -60,17 +60,17 @@ describe('Recover address', () => {
       'invalid signature length',
     )
 
-    // Signature with invalid recovery ID
+    // Signature with invalid recovery ID (v = 29)
     expect(() =>
       native.recoverAddress(
         DEFAULT_STATE,
-        '0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000',
+        '0x000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001d',
       ),
     ).toThrow('invalid recovery ID')
     expect(() =>
       wasm.recoverAddress(
         DEFAULT_STATE,
-        '0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000',
+        '0x000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001d',
       ),
     ).toThrow('invalid recovery ID')
 
@@ -83,4 +83,56 @@ describe('Recover address', () => {
       wasm.recoverAddress(DEFAULT_STATE, `0xf${signedState.signature.substr(13)}`),
     ).toThrow('invalid signature')
   })
+
+  test('Exposes error codes', async () => {
+    for (const recoverAddress of [native.recoverAddress, wasm.recoverAddress]) {
+      try {
+        recoverAddress(DEFAULT_STATE, '0x00')
+        fail('expected recoverAddress to throw')
+      } catch (error) {
+        expect(error.code).toStrictEqual('INVALID_SIGNATURE_LENGTH')
+        expect(error.message).toStrictEqual(
+          'invalid signature length: expected 64 or 65 bytes, got 1',
+        )
+      }
+    }
+  })
+
+  test('Accepts all conventions for v', async () => {
+    const { signature } = native.signState(DEFAULT_STATE, PRIVATE_KEY)
+    const address = native.recoverAddress(DEFAULT_STATE, signature)
+    const recoveryId = parseInt(signature.slice(-2), 16) - 27
+
+    // Raw recovery IDs, Ethereum's 27/28 and EIP-155 with chain ID 1.
+    for (const v of [0, 27, 37]) {
+      const withV = signature.slice(0, -2) + (v + recoveryId).toString(16).padStart(2, '0')
+
+      expect(native.recoverAddress(DEFAULT_STATE, withV)).toStrictEqual(address)
+      expect(wasm.recoverAddress(DEFAULT_STATE, withV)).toStrictEqual(address)
+    }
+  })
+
+  test('Never panics on malformed signatures', async () => {
+    const { signature } = native.signState(DEFAULT_STATE, PRIVATE_KEY)
+    const signer = native.recoverAddress(DEFAULT_STATE, signature)
+
+    for (let i = 0; i < 100; i++) {
+      // Either truncate the signature or flip bits of one byte of `r || s`.
+      const bytes = utils.arrayify(signature)
+      const malformed = utils.hexlify(
+        i % 2 ? bytes.slice(0, i % 65) : bytes.map((b, j) => (j === i % 64 ? b ^ (i + 1) : b)),
+      )
+
+      for (const recoverAddress of [native.recoverAddress, wasm.recoverAddress]) {
+        let address: string | undefined
+        try {
+          address = recoverAddress(DEFAULT_STATE, malformed)
+        } catch (error) {
+          expect(error.code).toMatch(/^INVALID_/)
+          continue
+        }
+        expect(address).not.toStrictEqual(signer)
+      }
+    }
+  })
 })
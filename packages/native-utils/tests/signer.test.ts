@@ -0,0 +1,25 @@
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+import { CURRENT_STATE, PRIVATE_KEY2 } from './fixtures'
+
+const STATE = { ...CURRENT_STATE, turnNum: 1 }
+const ADDRESS = '0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1'
+
+describe('Signer', () => {
+  test('Exposes the address of the private key', () => {
+    expect(new native.Signer(PRIVATE_KEY2).address).toStrictEqual(ADDRESS)
+    expect(new wasm.Signer(PRIVATE_KEY2).address).toStrictEqual(ADDRESS)
+  })
+
+  test('Signs states like signState', () => {
+    const expected = native.signState(STATE, PRIVATE_KEY2)
+
+    expect(new native.Signer(PRIVATE_KEY2).signState(STATE)).toStrictEqual(expected)
+    expect(new wasm.Signer(PRIVATE_KEY2).signState(STATE).signature).toStrictEqual(expected.signature)
+  })
+
+  test('Catches invalid private key', () => {
+    expect(() => new native.Signer('0x00')).toThrow('invalid private key')
+    expect(() => new wasm.Signer('0x00')).toThrow('invalid private key')
+  })
+})
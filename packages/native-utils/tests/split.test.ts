@@ -0,0 +1,33 @@
+import { utils } from 'ethers'
+import { describeBindings, PRIVATE_KEY2 } from './fixtures'
+
+describe('Split signatures', () => {
+  describeBindings(lib => {
+    test('Splits and joins like ethers', () => {
+      for (const message of ['0x00', '0x01', '0x02', '0x03']) {
+        const signature = lib.signMessage(message, PRIVATE_KEY2)
+        const { r, s, v } = utils.splitSignature(signature)
+
+        expect(lib.splitSignature(signature)).toStrictEqual({ r, s, v })
+        expect(lib.joinSignature({ r, s, v })).toStrictEqual(signature)
+        expect(lib.splitSignature(lib.toCompactSignature(signature))).toStrictEqual({ r, s, v })
+      }
+    })
+
+    test("Accepts the objects returned by ethers' splitSignature", () => {
+      const signature = lib.signMessage('0x00', PRIVATE_KEY2)
+      const split = utils.splitSignature(signature)
+
+      expect(lib.joinSignature(split)).toStrictEqual(signature)
+    })
+
+    test('Rejects invalid split signatures', () => {
+      const { r, s } = utils.splitSignature(lib.signMessage('0x00', PRIVATE_KEY2))
+
+      expect(() => lib.joinSignature({ r, s, v: 29 })).toThrow('signature: invalid recovery ID: 29')
+      expect(() => lib.joinSignature({ r: '0x00', s, v: 27 })).toThrow(
+        expect.objectContaining({ code: 'INVALID_INPUT' }),
+      )
+    })
+  })
+})
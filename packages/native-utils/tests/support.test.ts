@@ -0,0 +1,110 @@
+import { State } from '@statechannels/nitro-protocol'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+import { CHANNEL, PRIVATE_KEY1, PRIVATE_KEY2 } from './fixtures'
+
+const FIXED_PART = {
+  appDefinition: '0x0000000000000000000000000000000000000000',
+  challengeDuration: 1,
+}
+
+const OUTCOME = [
+  {
+    assetHolderAddress: '0x0000000000000000000000000000000000000000',
+    allocationItems: [
+      {
+        destination: '0x00000000000000000000000063FaC9201494f0bd17B9892B9fae4d52fe3BD377',
+        amount: '5',
+      },
+    ],
+  },
+]
+
+function variablePart(turnNum: number, isFinal = false) {
+  return {
+    turnNum,
+    isFinal,
+    outcome: OUTCOME,
+    appData: '0x00',
+  }
+}
+
+function toState(variables: ReturnType<typeof variablePart>): State {
+  return {
+    ...variables,
+    ...FIXED_PART,
+    channel: CHANNEL,
+  }
+}
+
+describe('Validate support proof', () => {
+  test('Single state signed by everyone', async () => {
+    const variableParts = [variablePart(1)]
+    const signatures = [PRIVATE_KEY1, PRIVATE_KEY2].map(
+      key => native.signState(toState(variableParts[0]), key).signature,
+    )
+
+    for (const utils of [native, wasm]) {
+      expect(
+        utils.validateSupportProof(CHANNEL, FIXED_PART, variableParts, signatures, [0, 0]),
+      ).toEqual('True')
+    }
+  })
+
+  test('Chain of states signed by their movers', async () => {
+    const variableParts = [variablePart(5), variablePart(6)]
+    // Participant 0 moves on even turns, participant 1 on odd turns
+    const signatures = [
+      native.signState(toState(variableParts[1]), PRIVATE_KEY1).signature,
+      native.signState(toState(variableParts[0]), PRIVATE_KEY2).signature,
+    ]
+
+    for (const utils of [native, wasm]) {
+      expect(
+        utils.validateSupportProof(CHANNEL, FIXED_PART, variableParts, signatures, [1, 0]),
+      ).toEqual('NeedToCheckApp')
+    }
+  })
+
+  test('Unacceptable whoSignedWhat fails', async () => {
+    const variableParts = [variablePart(5), variablePart(6)]
+    const signatures = [
+      native.signState(toState(variableParts[0]), PRIVATE_KEY1).signature,
+      native.signState(toState(variableParts[0]), PRIVATE_KEY2).signature,
+    ]
+
+    for (const utils of [native, wasm]) {
+      expect(() =>
+        utils.validateSupportProof(CHANNEL, FIXED_PART, variableParts, signatures, [0, 0]),
+      ).toThrow('Unacceptable whoSignedWhat array')
+    }
+  })
+
+  test('Signer mismatch fails', async () => {
+    const variableParts = [variablePart(5), variablePart(6)]
+    const signatures = [
+      native.signState(toState(variableParts[1]), PRIVATE_KEY2).signature,
+      native.signState(toState(variableParts[0]), PRIVATE_KEY1).signature,
+    ]
+
+    for (const utils of [native, wasm]) {
+      expect(() =>
+        utils.validateSupportProof(CHANNEL, FIXED_PART, variableParts, signatures, [1, 0]),
+      ).toThrow('Signature verification failed')
+    }
+  })
+
+  test('Non-consecutive turn numbers fail', async () => {
+    const variableParts = [variablePart(4), variablePart(6)]
+    const signatures = [
+      native.signState(toState(variableParts[1]), PRIVATE_KEY1).signature,
+      native.signState(toState(variableParts[0]), PRIVATE_KEY2).signature,
+    ]
+
+    for (const utils of [native, wasm]) {
+      expect(() =>
+        utils.validateSupportProof(CHANNEL, FIXED_PART, variableParts, signatures, [1, 0]),
+      ).toThrow('turnNum must increment by one')
+    }
+  })
+})
@@ -63,7 +63,7 @@ describe('Validate state transitions', () => {
     currentState.turnNum = 1;
     peerState.turnNum = 2;
 
-    const nativeSigned = native.signState(peerState, PRIVATE_KEY2);
+    const nativeSigned = native.signState(peerState, PRIVATE_KEY1);
     expect(native.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toEqual("True")
     expect(wasm.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toEqual("True")
   })
@@ -80,7 +80,7 @@ describe('Validate state transitions', () => {
     currentState.turnNum = 5;
     peerState.turnNum = 6;
 
-    const nativeSigned = native.signState(peerState, PRIVATE_KEY2);
+    const nativeSigned = native.signState(peerState, PRIVATE_KEY1);
     expect(native.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toEqual("NeedToCheckApp")
     expect(wasm.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toEqual("NeedToCheckApp")
 
@@ -95,11 +95,22 @@ describe('Validate state transitions', () => {
       ...NEXT_STATE,
     }
 
-    const nativeSigned = native.signState(peerState, PRIVATE_KEY1);
+    const nativeSigned = native.signState(peerState, PRIVATE_KEY2);
     expect(() => native.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toThrow('Signature verification failed');
     expect(() => wasm.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toThrow('Signature verification failed');
   });
 
+  test('channels without participants fail without panicking', async () => {
+    const nativeSigned = native.signState(NEXT_STATE, PRIVATE_KEY1);
+
+    const noParticipants = { ...NEXT_STATE, channel: { ...NEXT_STATE.channel, participants: [] } };
+    expect(() => native.validatePeerUpdate(CURRENT_STATE, noParticipants, nativeSigned.signature)).toThrow('channel has no participants');
+    expect(() => wasm.validatePeerUpdate(CURRENT_STATE, noParticipants, nativeSigned.signature)).toThrow('channel has no participants');
+
+    // The module is still usable afterwards.
+    expect(wasm.validatePeerUpdate(CURRENT_STATE, NEXT_STATE, nativeSigned.signature)).toEqual("NeedToCheckApp")
+  });
+
   test('turn number mismatch fails', async () => {
     let currentState = {
       ...CURRENT_STATE,
@@ -111,7 +122,7 @@ describe('Validate state transitions', () => {
 
     currentState.turnNum = 4;
     peerState.turnNum = 6;
-    const nativeSigned4 = native.signState(peerState, PRIVATE_KEY2);
+    const nativeSigned4 = native.signState(peerState, PRIVATE_KEY1);
     expect(() => native.validatePeerUpdate(currentState, peerState, nativeSigned4.signature)).toThrow('turnNum must increment by one');
     expect(() => wasm.validatePeerUpdate(currentState, peerState, nativeSigned4.signature)).toThrow('turnNum must increment by one');
   });
@@ -129,7 +140,7 @@ describe('Validate state transitions', () => {
     currentState.isFinal = true;
     peerState.turnNum = 6;
     peerState.isFinal = false;
-    const nativeSigned = native.signState(peerState, PRIVATE_KEY2);
+    const nativeSigned = native.signState(peerState, PRIVATE_KEY1);
     expect(() => native.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toThrow('transition from a final state to a non-final state');
     expect(() => wasm.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toThrow('transition from a final state to a non-final state');
   });
@@ -164,7 +175,7 @@ describe('Validate state transitions', () => {
     peerState.isFinal = true
     peerState.turnNum = 6;
  
-    const nativeSigned = native.signState(peerState, PRIVATE_KEY2);
+    const nativeSigned = native.signState(peerState, PRIVATE_KEY1);
 
     expect(() => native.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toThrow('Outcome change forbidden');
     expect(() => wasm.validatePeerUpdate(currentState, peerState, nativeSigned.signature)).toThrow('Outcome change forbidden');
@@ -0,0 +1,77 @@
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+// The example from EIP-712.
+const TYPED_DATA = {
+  types: {
+    EIP712Domain: [
+      { name: 'name', type: 'string' },
+      { name: 'version', type: 'string' },
+      { name: 'chainId', type: 'uint256' },
+      { name: 'verifyingContract', type: 'address' },
+    ],
+    Person: [
+      { name: 'name', type: 'string' },
+      { name: 'wallet', type: 'address' },
+    ],
+    Mail: [
+      { name: 'from', type: 'Person' },
+      { name: 'to', type: 'Person' },
+      { name: 'contents', type: 'string' },
+    ],
+  },
+  primaryType: 'Mail',
+  domain: {
+    name: 'Ether Mail',
+    version: '1',
+    chainId: 1,
+    verifyingContract: '0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC',
+  },
+  message: {
+    from: { name: 'Cow', wallet: '0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826' },
+    to: { name: 'Bob', wallet: '0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB' },
+    contents: 'Hello, Bob!',
+  },
+}
+
+// keccak256('cow')
+const PRIVATE_KEY = '0xc85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4'
+const ADDRESS = '0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826'
+
+const HASH = '0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2'
+const SIGNATURE =
+  '0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d' +
+  '07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562' +
+  '1c'
+
+describe('Typed data', () => {
+  test('Hashes typed data', () => {
+    expect(native.hashTypedData(TYPED_DATA)).toStrictEqual(HASH)
+    expect(wasm.hashTypedData(TYPED_DATA)).toStrictEqual(HASH)
+  })
+
+  test('Derives the domain type if it is missing', () => {
+    const { EIP712Domain, ...types } = TYPED_DATA.types
+    const typedData = { ...TYPED_DATA, types }
+
+    expect(native.hashTypedData(typedData)).toStrictEqual(HASH)
+    expect(wasm.hashTypedData(typedData)).toStrictEqual(HASH)
+  })
+
+  test('Signs typed data', () => {
+    expect(native.signTypedData(TYPED_DATA, PRIVATE_KEY)).toStrictEqual(SIGNATURE)
+    expect(wasm.signTypedData(TYPED_DATA, PRIVATE_KEY)).toStrictEqual(SIGNATURE)
+  })
+
+  test('Recovers typed data signers', () => {
+    expect(native.recoverTypedDataSigner(TYPED_DATA, SIGNATURE)).toStrictEqual(ADDRESS)
+    expect(wasm.recoverTypedDataSigner(TYPED_DATA, SIGNATURE)).toStrictEqual(ADDRESS)
+  })
+
+  test('Catches invalid typed data', () => {
+    const typedData = { ...TYPED_DATA, primaryType: 'Letter' }
+
+    expect(() => native.hashTypedData(typedData)).toThrow('invalid typed data: unknown type Letter')
+    expect(() => wasm.hashTypedData(typedData)).toThrow('invalid typed data: unknown type Letter')
+  })
+})
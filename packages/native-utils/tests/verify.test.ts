@@ -0,0 +1,63 @@
+import { State } from '@statechannels/nitro-protocol'
+import * as wasm from '@statechannels/wasm-utils'
+import * as native from '..'
+
+const DEFAULT_STATE: State = {
+  turnNum: 1,
+  isFinal: false,
+  channel: {
+    chainId: '1',
+    channelNonce: 1,
+    participants: ['0x19E7E376E7C213B7E7e7e46cc70A5dD086DAff2A'],
+  },
+  challengeDuration: 1,
+  outcome: [
+    {
+      assetHolderAddress: '0x0000000000000000000000000000000000000000',
+      allocationItems: [
+        {
+          destination:
+            '0x0000000000000000000000000000000000000000000000000000000000000000',
+          amount: '1',
+        },
+      ],
+    },
+  ],
+  appDefinition: '0x0000000000000000000000000000000000000000',
+  appData: '0x0000000000000000000000000000000000000000000000000000000000000000',
+}
+
+const PRIVATE_KEY = '0x1111111111111111111111111111111111111111111111111111111111111111'
+const SIGNER_ADDRESS = '0x19E7E376E7C213B7E7e7e46cc70A5dD086DAff2A'
+const OTHER_ADDRESS = '0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1'
+
+describe('Verify signature', () => {
+  test('Accepts the signer address', async () => {
+    const { hash, signature } = native.signState(DEFAULT_STATE, PRIVATE_KEY)
+
+    expect(native.verifySignature(hash, SIGNER_ADDRESS, signature)).toBe(true)
+    expect(wasm.verifySignature(hash, SIGNER_ADDRESS, signature)).toBe(true)
+
+    // Address casing does not matter
+    expect(native.verifySignature(hash, SIGNER_ADDRESS.toLowerCase(), signature)).toBe(true)
+    expect(wasm.verifySignature(hash, SIGNER_ADDRESS.toLowerCase(), signature)).toBe(true)
+  })
+
+  test('Rejects other addresses', async () => {
+    const { hash, signature } = native.signState(DEFAULT_STATE, PRIVATE_KEY)
+
+    expect(native.verifySignature(hash, OTHER_ADDRESS, signature)).toBe(false)
+    expect(wasm.verifySignature(hash, OTHER_ADDRESS, signature)).toBe(false)
+  })
+
+  test('Catches invalid signatures', async () => {
+    const { hash } = native.signState(DEFAULT_STATE, PRIVATE_KEY)
+
+    expect(() => native.verifySignature(hash, SIGNER_ADDRESS, '0x00')).toThrow(
+      'invalid signature length',
+    )
+    expect(() => wasm.verifySignature(hash, SIGNER_ADDRESS, '0x00')).toThrow(
+      'invalid signature length',
+    )
+  })
+})
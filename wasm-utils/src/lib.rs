@@ -1,18 +1,84 @@
 use std::ops::Deref;
 
-use js_sys::{JsString};
+use js_sys::{Array, Error as JsError, JsString, Reflect};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 use wasm_bindgen::prelude::*;
 
-use statechannels_native_utils_common::prelude::{hash_message as do_hash_message, *};
+use statechannels_native_utils_common::prelude::{
+    derive_key_from_mnemonic as do_derive_key_from_mnemonic,
+    derive_key_from_seed as do_derive_key_from_seed, hash_message as do_hash_message,
+    recover_message_signer as do_recover_message_signer, register_app as do_register_app,
+    sign_message as do_sign_message, unregister_app as do_unregister_app,
+    verify_signature as do_verify_signature, *,
+};
 
 #[wasm_bindgen(typescript_custom_section)]
 const TS_APPEND_CONTENT: &'static str = r#"
-import { Channel, State } from '@statechannels/nitro-protocol';
+import { AllocationAssetOutcome, AllocationItem, Channel, Guarantee, Outcome, State } from '@statechannels/nitro-protocol';
 
 interface StateSignature {
     hash: string
     signature: string
 }
+
+interface SplitSignature {
+    r: string
+    s: string
+    v: number
+}
+
+interface TransferPayouts {
+    newAllocation: AllocationItem[]
+    payouts: string[]
+    totalPayouts: string
+    safeToDelete: boolean
+}
+
+interface ClaimPayouts {
+    newAllocation: AllocationItem[]
+    payouts: string[]
+    totalPayouts: string
+    remainingHoldings: string
+}
+
+interface DepositSafety {
+    expectedHeld: string
+    amountRequired: string
+    safeToDeposit: boolean
+}
+
+interface DerivedKey {
+    privateKey: string
+    address: string
+}
+
+interface TypedData {
+    types: Record<string, { name: string; type: string }[]>
+    primaryType: string
+    domain: Record<string, unknown>
+    message: Record<string, unknown>
+}
+
+interface ParseOptions {
+    strictChecksums?: boolean
+}
+
+interface ValidationOptions {
+    validateOutcome?: boolean
+}
+
+interface FixedPart {
+    appDefinition: string
+    challengeDuration: number
+}
+
+interface StateVariables {
+    turnNum: number
+    isFinal: boolean
+    outcome: Outcome
+    appData: string
+}
 "#;
 
 #[wasm_bindgen]
@@ -23,69 +89,457 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "Channel")]
     pub type JsChannel;
 
+    #[wasm_bindgen(typescript_type = "Outcome")]
+    pub type JsOutcome;
+
     #[wasm_bindgen(typescript_type = "StateSignature")]
     pub type JsStateSignature;
+
+    #[wasm_bindgen(typescript_type = "SplitSignature")]
+    pub type JsSplitSignature;
+
+    #[wasm_bindgen(typescript_type = "string | SplitSignature")]
+    pub type JsSignature;
+
+    #[wasm_bindgen(typescript_type = "DerivedKey")]
+    pub type JsDerivedKey;
+
+    #[wasm_bindgen(typescript_type = "TypedData")]
+    pub type JsTypedData;
+
+    #[wasm_bindgen(typescript_type = "AllocationAssetOutcome")]
+    pub type JsAllocationAssetOutcome;
+
+    #[wasm_bindgen(typescript_type = "TransferPayouts")]
+    pub type JsTransferPayouts;
+
+    #[wasm_bindgen(typescript_type = "Guarantee")]
+    pub type JsGuarantee;
+
+    #[wasm_bindgen(typescript_type = "ClaimPayouts")]
+    pub type JsClaimPayouts;
+
+    #[wasm_bindgen(typescript_type = "DepositSafety")]
+    pub type JsDepositSafety;
+
+    #[wasm_bindgen(typescript_type = "State[]")]
+    pub type JsStateArray;
+
+    #[wasm_bindgen(typescript_type = "(StateSignature | Error)[]")]
+    pub type JsStateSignatureOrErrorArray;
+
+    #[wasm_bindgen(typescript_type = "(string | Error)[]")]
+    pub type JsStringOrErrorArray;
+
+    #[wasm_bindgen(typescript_type = "FixedPart")]
+    pub type JsFixedPart;
+
+    #[wasm_bindgen(typescript_type = "ParseOptions")]
+    pub type JsParseOptions;
+
+    #[wasm_bindgen(typescript_type = "ParseOptions & ValidationOptions")]
+    pub type JsPeerUpdateOptions;
+
+    #[wasm_bindgen(typescript_type = "StateVariables[]")]
+    pub type JsStateVariablesArray;
+
+    #[wasm_bindgen(typescript_type = "string[]")]
+    pub type JsStringArray;
+
+    #[wasm_bindgen(typescript_type = "number[]")]
+    pub type JsNumberArray;
+}
+
+fn to_js_error(error: Error) -> JsValue {
+    let js_error = JsError::new(&error.to_string());
+    Reflect::set(&js_error, &"code".into(), &error.code().into()).unwrap();
+    js_error.into()
+}
+
+fn parse_options<T: DeserializeOwned + Default>(options: Option<&JsValue>) -> Result<T, JsValue> {
+    match options {
+        Some(options) => from_js(options, "options"),
+        None => Ok(T::default()),
+    }
+}
+
+fn parse<T: DeserializeOwned>(value: &JsValue, name: &str, options: &ParseOptions) -> Result<T, JsValue> {
+    let value: Value = value.into_serde().map_err(|error| {
+        to_js_error(Error::InvalidInput {
+            path: name.into(),
+            message: error.to_string(),
+        })
+    })?;
+    parse_value(value, name, options).map_err(to_js_error)
+}
+
+fn from_js<T: DeserializeOwned>(value: &JsValue, name: &str) -> Result<T, JsValue> {
+    parse(value, name, &ParseOptions::default())
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    JsValue::from_serde(value).map_err(|error| JsError::new(&error.to_string()).into())
+}
+
+/// Converts the results of a batch to an array holding either the value or
+/// the error of each item.
+fn to_js_results<T: Serialize>(results: impl Iterator<Item = Result<T, Error>>) -> Result<JsValue, JsValue> {
+    let array = Array::new();
+    for result in results {
+        array.push(&match result {
+            Ok(value) => to_js(&value)?,
+            Err(error) => to_js_error(error),
+        });
+    }
+    Ok(array.into())
+}
+
+/// Deserializes the state at index `i` of a batch, so that one malformed state
+/// fails only its own slot of the result.
+fn parse_state(state: Value, i: usize) -> Result<State, Error> {
+    parse_value(state, &format!("states[{}]", i), &ParseOptions::default())
 }
 
 #[wasm_bindgen(js_name = "getChannelId")]
-pub fn get_channel_id(channel: &JsChannel) -> JsString {
-    let channel: Channel = channel.into_serde().unwrap();
-    channel.id().to_hex_string().into()
+pub fn get_channel_id(channel: &JsChannel, options: Option<JsParseOptions>) -> Result<JsString, JsValue> {
+    let channel: Channel = parse(channel, "channel", &parse_options(options.as_deref())?)?;
+    Ok(channel.id().to_hex_string().into())
 }
 
 #[wasm_bindgen(js_name = "encodeOutcome")]
-pub fn encode_outcome(state: &JsState) -> JsString {
-    let state: State = state.into_serde().unwrap();
-    state.outcome.encode().to_hex_string().into()
+pub fn encode_outcome(state: &JsState) -> Result<JsString, JsValue> {
+    let state: State = from_js(state, "state")?;
+    Ok(state.outcome.encode().to_hex_string().into())
+}
+
+#[wasm_bindgen(js_name = "decodeOutcome")]
+pub fn decode_outcome(outcome: &JsString) -> Result<JsOutcome, JsValue> {
+    let outcome: Bytes = from_js(outcome, "outcome")?;
+    let outcome = Outcome::decode(&outcome).map_err(to_js_error)?;
+    Ok(to_js(&outcome)?.into())
+}
+
+#[wasm_bindgen(js_name = "computeTransferPayouts")]
+pub fn compute_transfer_payouts(
+    asset_outcome: &JsAllocationAssetOutcome,
+    holdings: &JsString,
+    indices: Option<JsNumberArray>,
+) -> Result<JsTransferPayouts, JsValue> {
+    let asset_outcome: AllocationAssetOutcome = from_js(asset_outcome, "assetOutcome")?;
+    let holdings: Uint256 = from_js(holdings, "holdings")?;
+    let indices: Vec<usize> = match indices {
+        Some(indices) => from_js(&indices, "indices")?,
+        None => Vec::new(),
+    };
+    let payouts = asset_outcome
+        .compute_transfer_payouts(&holdings, &indices)
+        .map_err(to_js_error)?;
+    Ok(to_js(&payouts)?.into())
+}
+
+#[wasm_bindgen(js_name = "computeClaimPayouts")]
+pub fn compute_claim_payouts(
+    guarantee: &JsGuarantee,
+    target: &JsAllocationAssetOutcome,
+    holdings: &JsString,
+) -> Result<JsClaimPayouts, JsValue> {
+    let guarantee: Guarantee = from_js(guarantee, "guarantee")?;
+    let target: AllocationAssetOutcome = from_js(target, "target")?;
+    let holdings: Uint256 = from_js(holdings, "holdings")?;
+    let payouts = guarantee.compute_claim_payouts(&target, &holdings);
+    Ok(to_js(&payouts)?.into())
+}
+
+#[wasm_bindgen(js_name = "computeDepositSafety")]
+pub fn compute_deposit_safety(
+    asset_outcome: &JsAllocationAssetOutcome,
+    destination: &JsString,
+    holdings: &JsString,
+) -> Result<JsDepositSafety, JsValue> {
+    let asset_outcome: AllocationAssetOutcome = from_js(asset_outcome, "assetOutcome")?;
+    let destination: Bytes32 = from_js(destination, "destination")?;
+    let holdings: Uint256 = from_js(holdings, "holdings")?;
+    let safety = asset_outcome
+        .deposit_safety(&destination, &holdings)
+        .map_err(to_js_error)?;
+    Ok(to_js(&safety)?.into())
 }
 
 #[wasm_bindgen(js_name = "hashAppPart")]
-pub fn hash_app_part(state: &JsState) -> JsString {
-    let state: State = state.into_serde().unwrap();
-    state.hash_app_part().to_hex_string().into()
+pub fn hash_app_part(state: &JsState) -> Result<JsString, JsValue> {
+    let state: State = from_js(state, "state")?;
+    Ok(state.hash_app_part().to_hex_string().into())
 }
 
 #[wasm_bindgen(js_name = "hashOutcome")]
-pub fn hash_outcome(state: &JsState) -> JsString {
-    let state: State = state.into_serde().unwrap();
-    state.outcome.hash().to_hex_string().into()
+pub fn hash_outcome(state: &JsState) -> Result<JsString, JsValue> {
+    let state: State = from_js(state, "state")?;
+    Ok(state.outcome.hash().to_hex_string().into())
 }
 
 #[wasm_bindgen(js_name = "hashState")]
-pub fn hash_state(state: &JsState) -> JsString {
-    let state: State = state.into_serde().unwrap();
-    state.hash().to_hex_string().into()
+pub fn hash_state(state: &JsState, options: Option<JsParseOptions>) -> Result<JsString, JsValue> {
+    let state: State = parse(state, "state", &parse_options(options.as_deref())?)?;
+    Ok(state.hash().to_hex_string().into())
 }
 
 #[wasm_bindgen(js_name = "hashMessage")]
-pub fn hash_message(msg: &JsString) -> JsString {
-    let msg = msg.into_serde::<Bytes>().unwrap();
-    Bytes32::from(do_hash_message(&msg.deref()))
+pub fn hash_message(msg: &JsString) -> Result<JsString, JsValue> {
+    let msg: Bytes = from_js(msg, "msg")?;
+    Ok(Bytes32::from(do_hash_message(&msg.deref()))
         .to_hex_string()
-        .into()
+        .into())
 }
 
 #[wasm_bindgen(js_name = "signState")]
-pub fn sign_state(state: &JsState, private_key: &JsString) -> Result<JsStateSignature, JsValue> {
-    let state: State = state.into_serde().unwrap();
-    let private_key: Bytes = private_key.into_serde().unwrap();
-    let signature = state.sign(private_key).map_err(JsValue::from)?;
-    Ok(JsValue::from_serde(&signature).unwrap().into())
+pub fn sign_state(
+    state: &JsState,
+    private_key: &JsString,
+    options: Option<JsParseOptions>,
+) -> Result<JsStateSignature, JsValue> {
+    let state: State = parse(state, "state", &parse_options(options.as_deref())?)?;
+    let private_key: Bytes = from_js(private_key, "privateKey")?;
+    let signature = state.sign(private_key).map_err(to_js_error)?;
+    Ok(to_js(&signature)?.into())
+}
+
+#[wasm_bindgen(js_name = "hashStates")]
+pub fn hash_states(states: &JsStateArray) -> Result<JsStringOrErrorArray, JsValue> {
+    let states: Vec<Value> = from_js(states, "states")?;
+    let hashes = states
+        .into_iter()
+        .enumerate()
+        .map(|(i, state)| Ok(parse_state(state, i)?.hash().to_hex_string()));
+    Ok(to_js_results(hashes)?.into())
+}
+
+#[wasm_bindgen(js_name = "signStates")]
+pub fn sign_states(states: &JsStateArray, private_key: &JsString) -> Result<JsStateSignatureOrErrorArray, JsValue> {
+    let states: Vec<Value> = from_js(states, "states")?;
+    let private_key: Bytes = from_js(private_key, "privateKey")?;
+    let secret_key = parse_private_key(&private_key).map_err(to_js_error)?;
+    let signatures = states
+        .into_iter()
+        .enumerate()
+        .map(|(i, state)| Ok(parse_state(state, i)?.sign_with_secret_key(&secret_key)));
+    Ok(to_js_results(signatures)?.into())
+}
+
+#[wasm_bindgen(js_name = "recoverAddresses")]
+pub fn recover_addresses(states: &JsStateArray, signatures: &JsStringArray) -> Result<JsStringOrErrorArray, JsValue> {
+    let states: Vec<Value> = from_js(states, "states")?;
+    let signatures: Vec<Bytes> = from_js(signatures, "signatures")?;
+    if states.len() != signatures.len() {
+        return Err(to_js_error(Error::BatchLengthMismatch {
+            expected: states.len(),
+            actual: signatures.len(),
+        }));
+    }
+
+    let addresses = states
+        .into_iter()
+        .zip(signatures)
+        .enumerate()
+        .map(|(i, (state, signature))| parse_state(state, i)?.recover_address(signature));
+    Ok(to_js_results(addresses)?.into())
 }
 
 #[wasm_bindgen(js_name = "recoverAddress")]
-pub fn recover_address(state: &JsState, signature: &JsString) -> Result<JsString, JsValue> {
-    let state: State = state.into_serde().unwrap();
-    let signature: Bytes = signature.into_serde().unwrap();
-    let address = state.recover_address(signature).map_err(JsValue::from)?;
-    Ok(JsValue::from_serde(&address).unwrap().into())
+pub fn recover_address(
+    state: &JsState,
+    signature: &JsString,
+    options: Option<JsParseOptions>,
+) -> Result<JsString, JsValue> {
+    let state: State = parse(state, "state", &parse_options(options.as_deref())?)?;
+    let signature: Bytes = from_js(signature, "signature")?;
+    let address = state.recover_address(signature).map_err(to_js_error)?;
+    Ok(address.into())
+}
+
+#[wasm_bindgen(js_name = "verifySignature")]
+pub fn verify_signature(hash: &JsString, address: &JsString, signature: &JsString) -> Result<bool, JsValue> {
+    let hash: Bytes32 = from_js(hash, "hash")?;
+    let address: Address = from_js(address, "address")?;
+    let signature: Bytes = from_js(signature, "signature")?;
+    do_verify_signature(hash, address, signature).map_err(to_js_error)
+}
+
+#[wasm_bindgen(js_name = "toCompactSignature")]
+pub fn to_compact_signature(signature: &JsString) -> Result<JsString, JsValue> {
+    let signature: Bytes = from_js(signature, "signature")?;
+    let signature = RecoverableSignature::from_slice(&signature).map_err(to_js_error)?;
+    Ok(signature.to_compact().to_hex_string().into())
+}
+
+#[wasm_bindgen(js_name = "fromCompactSignature")]
+pub fn from_compact_signature(signature: &JsString) -> Result<JsString, JsValue> {
+    let signature: Bytes = from_js(signature, "signature")?;
+    let signature = RecoverableSignature::from_slice(&signature).map_err(to_js_error)?;
+    Ok(signature.as_bytes().to_hex_string().into())
+}
+
+#[wasm_bindgen(js_name = "splitSignature")]
+pub fn split_signature(signature: &JsSignature) -> Result<JsSplitSignature, JsValue> {
+    let signature: RecoverableSignature = from_js(signature, "signature")?;
+    Ok(to_js(&signature.split())?.into())
+}
+
+#[wasm_bindgen(js_name = "joinSignature")]
+pub fn join_signature(signature: &JsSignature) -> Result<JsString, JsValue> {
+    let signature: RecoverableSignature = from_js(signature, "signature")?;
+    Ok(signature.as_bytes().to_hex_string().into())
+}
+
+#[wasm_bindgen(js_name = "signMessage")]
+pub fn sign_message(message: &JsString, private_key: &JsString) -> Result<JsString, JsValue> {
+    let message: Bytes = from_js(message, "message")?;
+    let private_key: Bytes = from_js(private_key, "privateKey")?;
+    let secret_key = parse_private_key(&private_key).map_err(to_js_error)?;
+    let signature = do_sign_message(&message, &secret_key);
+    Ok(signature.as_bytes().to_hex_string().into())
+}
+
+#[wasm_bindgen(js_name = "recoverMessageSigner")]
+pub fn recover_message_signer(message: &JsString, signature: &JsString) -> Result<JsString, JsValue> {
+    let message: Bytes = from_js(message, "message")?;
+    let signature: Bytes = from_js(signature, "signature")?;
+    let address = do_recover_message_signer(&message, &signature).map_err(to_js_error)?;
+    Ok(address.into())
+}
+
+#[wasm_bindgen(js_name = "hashTypedData")]
+pub fn hash_typed_data(typed_data: &JsTypedData) -> Result<JsString, JsValue> {
+    let typed_data: TypedData = from_js(typed_data, "typedData")?;
+    let hash = typed_data.hash().map_err(to_js_error)?;
+    Ok(hash.to_hex_string().into())
+}
+
+#[wasm_bindgen(js_name = "signTypedData")]
+pub fn sign_typed_data(typed_data: &JsTypedData, private_key: &JsString) -> Result<JsString, JsValue> {
+    let typed_data: TypedData = from_js(typed_data, "typedData")?;
+    let private_key: Bytes = from_js(private_key, "privateKey")?;
+    let secret_key = parse_private_key(&private_key).map_err(to_js_error)?;
+    let signature = typed_data.sign(&secret_key).map_err(to_js_error)?;
+    Ok(signature.as_bytes().to_hex_string().into())
+}
+
+#[wasm_bindgen(js_name = "recoverTypedDataSigner")]
+pub fn recover_typed_data_signer(typed_data: &JsTypedData, signature: &JsString) -> Result<JsString, JsValue> {
+    let typed_data: TypedData = from_js(typed_data, "typedData")?;
+    let signature: Bytes = from_js(signature, "signature")?;
+    let address = typed_data.recover_signer(&signature).map_err(to_js_error)?;
+    Ok(address.into())
+}
+
+#[wasm_bindgen(js_name = "getAddressFromPrivateKey")]
+pub fn get_address_from_private_key(private_key: &JsString) -> Result<String, JsValue> {
+    let private_key: Bytes = from_js(private_key, "privateKey")?;
+    address_from_private_key(&private_key).map_err(to_js_error)
+}
+
+#[wasm_bindgen(js_name = "getAddressFromPublicKey")]
+pub fn get_address_from_public_key(public_key: &JsString) -> Result<String, JsValue> {
+    let public_key: Bytes = from_js(public_key, "publicKey")?;
+    address_from_public_key(&public_key).map_err(to_js_error)
+}
+
+#[wasm_bindgen(js_name = "deriveKeyFromSeed")]
+pub fn derive_key_from_seed(seed: &JsString, index: u32) -> Result<JsDerivedKey, JsValue> {
+    let seed: Bytes = from_js(seed, "seed")?;
+    let key = do_derive_key_from_seed(&seed, index).map_err(to_js_error)?;
+    Ok(to_js(&key)?.into())
+}
+
+#[wasm_bindgen(js_name = "deriveKeyFromMnemonic")]
+pub fn derive_key_from_mnemonic(
+    mnemonic: &str,
+    index: u32,
+    passphrase: Option<String>,
+) -> Result<JsDerivedKey, JsValue> {
+    let passphrase = passphrase.unwrap_or_default();
+    let key = do_derive_key_from_mnemonic(mnemonic, &passphrase, index).map_err(to_js_error)?;
+    Ok(to_js(&key)?.into())
 }
 
 #[wasm_bindgen(js_name = "validatePeerUpdate")]
-pub fn validate_peer_update(state: &JsState, peer_update: &JsState, signature: &JsString) -> Result<JsString, JsValue> {
-    let state: State = state.into_serde().unwrap();
-    let peer_update: State = peer_update.into_serde().unwrap();
-    let signature: Bytes = signature.into_serde().unwrap();
-    let result = state.validate_peer_update(peer_update, signature).map_err(JsValue::from)?;
-    Ok(JsValue::from_serde(&result).unwrap().into())
-}
\ No newline at end of file
+pub fn validate_peer_update(
+    state: &JsState,
+    peer_update: &JsState,
+    signature: &JsString,
+    options: Option<JsPeerUpdateOptions>,
+) -> Result<JsString, JsValue> {
+    let options: PeerUpdateOptions = parse_options(options.as_deref())?;
+    let state: State = parse(state, "state", &options.parse)?;
+    let peer_update: State = parse(peer_update, "peerUpdate", &options.parse)?;
+    let signature: Bytes = from_js(signature, "signature")?;
+    let result = state
+        .validate_peer_update_with_options(peer_update, signature, &options.validation)
+        .map_err(to_js_error)?;
+    Ok(to_js(&result)?.into())
+}
+
+#[wasm_bindgen(js_name = "validateSupportProof")]
+pub fn validate_support_proof(
+    channel: &JsChannel,
+    fixed_part: &JsFixedPart,
+    variable_parts: &JsStateVariablesArray,
+    signatures: &JsStringArray,
+    who_signed_what: &JsNumberArray,
+    options: Option<JsParseOptions>,
+) -> Result<JsString, JsValue> {
+    let options = parse_options(options.as_deref())?;
+    let channel: Channel = parse(channel, "channel", &options)?;
+    let fixed_part: FixedPart = parse(fixed_part, "fixedPart", &options)?;
+    let variable_parts: Vec<StateVariables> = parse(variable_parts, "variableParts", &options)?;
+    let signatures: Vec<Bytes> = from_js(signatures, "signatures")?;
+    let who_signed_what: Vec<u8> = from_js(who_signed_what, "whoSignedWhat")?;
+    let result = channel
+        .validate_support_proof(fixed_part, variable_parts, signatures, who_signed_what)
+        .map_err(to_js_error)?;
+    Ok(to_js(&result)?.into())
+}
+
+#[wasm_bindgen(js_name = "registerApp")]
+pub fn register_app(app_definition: &JsString, name: &JsString) -> Result<(), JsValue> {
+    let app_definition: Address = from_js(app_definition, "appDefinition")?;
+    let name: String = name.into();
+    do_register_app(app_definition, builtin_app(&name).map_err(to_js_error)?);
+    Ok(())
+}
+
+#[wasm_bindgen(js_name = "unregisterApp")]
+pub fn unregister_app(app_definition: &JsString) -> Result<bool, JsValue> {
+    let app_definition: Address = from_js(app_definition, "appDefinition")?;
+    Ok(do_unregister_app(&app_definition))
+}
+
+#[wasm_bindgen(js_name = "Signer")]
+pub struct JsSigner(Signer);
+
+#[wasm_bindgen(js_class = "Signer")]
+impl JsSigner {
+    #[wasm_bindgen(constructor)]
+    pub fn new(private_key: &JsString) -> Result<JsSigner, JsValue> {
+        let private_key: Bytes = from_js(private_key, "privateKey")?;
+        let signer = Signer::new(private_key).map_err(to_js_error)?;
+        Ok(JsSigner(signer))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.0.checksummed_address()
+    }
+
+    #[wasm_bindgen(js_name = "signState")]
+    pub fn sign_state(&self, state: &JsState) -> Result<JsStateSignature, JsValue> {
+        let state: State = from_js(state, "state")?;
+        let signature = self.0.sign_state(&state);
+        Ok(to_js(&signature)?.into())
+    }
+
+    #[wasm_bindgen(js_name = "signMessage")]
+    pub fn sign_message(&self, message: &JsString) -> Result<JsString, JsValue> {
+        let message: Bytes = from_js(message, "message")?;
+        Ok(self.0.sign_message(&message).as_bytes().to_hex_string().into())
+    }
+}